@@ -1,11 +1,13 @@
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fs,
-    process::Command,
+    process::{self, Command, Stdio},
 };
-use std::io::{self, Write};
+use std::io::{self, Read, Write};
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use num_format::{Locale, ToFormattedString};
+use chrono::{Datelike, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, Timelike};
 
 /// A cron viewer that pretty-prints your crontab or shows histograms by hour, weekday, or month.
 #[derive(Parser)]
@@ -27,7 +29,7 @@ struct Args {
     #[arg(long = "chart-month")]
     chart_month: bool,
 
-    /// Detailed breakdown for a specific month (name or number)
+    /// Detailed breakdown for a specific month (name, abbreviation, or number)
     #[arg(long = "chart-month-detail", value_name = "MONTH")]
     chart_month_detail: Option<String>,
 
@@ -35,190 +37,1624 @@ struct Args {
     #[arg(long = "file", value_name = "FILE")]
     file: Option<String>,
 
-    /// Filter cron entries by substring match
+    /// With --file, also read entries from stdin and append them after the file's
+    #[arg(long = "also-stdin", requires = "file")]
+    also_stdin: bool,
+
+    /// Join lines ending in a backslash with the following line before parsing
+    #[arg(long)]
+    continuations: bool,
+
+    /// Filter cron entries by substring match (repeatable; any pattern may match)
     #[arg(long, value_name = "PATTERN")]
-    filter: Option<String>,
+    filter: Vec<String>,
+
+    /// Append entries from FILE to the loaded crontab, tagging them in the listing
+    #[arg(long, value_name = "FILE")]
+    overlay: Option<String>,
+
+    /// Treat crontab hours as UTC and show them in this offset (e.g. +02:00); affects
+    /// --chart, --svg, --on, --compact-charts and --dashline
+    #[arg(long, value_name = "OFFSET", allow_hyphen_values = true)]
+    tz: Option<FixedOffset>,
+
+    /// Write the hourly histogram as a standalone SVG bar chart to PATH
+    #[arg(long, value_name = "PATH")]
+    svg: Option<String>,
+
+    /// Encoding of the crontab input
+    #[arg(long, value_enum, value_name = "ENCODING", default_value = "utf8")]
+    encoding: Encoding,
+
+    /// Order of the five schedule fields in the input, e.g. m,h,dow,dom,mon
+    #[arg(
+        long = "field-order",
+        value_name = "SPEC",
+        value_parser = parse_field_order,
+        default_value = "m,h,dom,mon,dow"
+    )]
+    field_order: FieldOrder,
+
+    /// Select only the Nth entry (1-based, counted after --filter)
+    #[arg(long, value_name = "N")]
+    index: Option<usize>,
+
+    /// Render a random subset of N entries (after --filter), kept in file order
+    #[arg(long, value_name = "N")]
+    sample: Option<usize>,
+
+    /// Seed for --sample so the same subset is picked on every run
+    #[arg(long, value_name = "SEED", requires = "sample")]
+    seed: Option<u64>,
+
+    /// Print "EXPRESSION → description" for every entry, without color or commands
+    #[arg(long = "describe-all")]
+    describe_all: bool,
+
+    /// Render each entry with a template using {minute} {hour} {dom} {month} {dow}
+    /// {command} {human} {next} {freq}
+    #[arg(long, value_name = "STR")]
+    template: Option<String>,
+
+    /// Report entries that run the same command on the same expanded schedule
+    #[arg(long = "semantic-duplicates")]
+    semantic_duplicates: bool,
+
+    /// Check fields for reversed ranges and oversized lists; exits non-zero if any are found
+    #[arg(long)]
+    validate: bool,
+
+    /// Warn when jobs exist but MAILTO is unset, or is empty while some jobs would mail output
+    #[arg(long = "check-mailto")]
+    check_mailto: bool,
+
+    /// Pipe each entry to `sh -c CMD`; a non-zero exit is a policy violation (stderr is the reason)
+    #[arg(long = "validator-cmd", value_name = "CMD")]
+    validator_cmd: Option<String>,
+
+    /// Re-emit entries with single-spaced schedule fields, keeping commands verbatim
+    #[arg(long)]
+    tidy: bool,
+
+    /// Print the crontab as-is minus `#` comment lines (env lines, @specials and order kept)
+    #[arg(long = "strip-comments")]
+    strip_comments: bool,
+
+    /// Group jobs into a tree by executable (first command token)
+    #[arg(long)]
+    tree: bool,
+
+    /// Group jobs under frequency-class headers (Minutely, Hourly, Daily, ...)
+    #[arg(long = "by-frequency")]
+    by_frequency: bool,
+
+    /// Emit an `echo '<command>' | at` line for each job's next run, for migrating to at(1)
+    #[arg(long = "at-export")]
+    at_export: bool,
+
+    /// Append inferred tags (frequency, every-minute, redirection, path) to each job
+    #[arg(long)]
+    annotate: bool,
+
+    /// Show repeated entries once with an "(×N)" suffix
+    #[arg(long = "count-duplicates")]
+    count_duplicates: bool,
+
+    /// Phrase schedules in crontab.guru style (e.g. "At 03:00 on every day-of-month")
+    #[arg(long = "guru-style")]
+    guru_style: bool,
+
+    /// Draw charts with Unicode glyphs: auto (from locale), always, or never
+    #[arg(long, value_enum, value_name = "WHEN", default_value = "auto")]
+    unicode: UnicodeMode,
+
+    /// Where histogram rows show the count: before or after the bar
+    #[arg(long = "count-position", value_enum, value_name = "POS", default_value = "before")]
+    count_position: CountPosition,
+
+    /// Group fixed-minute jobs by the minute past the hour they fire at
+    #[arg(long = "describe-offset")]
+    describe_offset: bool,
+
+    /// Show hourly, weekday, and monthly distributions as three labelled sparklines
+    #[arg(long = "compact-charts")]
+    compact_charts: bool,
+
+    /// Print a one-line status summary (jobs, every-minute, @reboot, hourly sparkline)
+    #[arg(long)]
+    dashline: bool,
+
+    /// Group digits in histogram counts per locale (e.g. en → 1,234, de → 1.234)
+    #[arg(long, value_name = "CODE", value_parser = parse_locale)]
+    locale: Option<Locale>,
+
+    /// Mirror histograms for right-to-left terminals: bars grow leftward, labels on the right
+    #[arg(long)]
+    rtl: bool,
+
+    /// List jobs that fire on DATE (YYYY-MM-DD) with their firing times
+    #[arg(long, value_name = "DATE")]
+    on: Option<NaiveDate>,
+
+    /// Draw a 24-hour timeline per job, marking when it fires (width from $COLUMNS)
+    #[arg(long)]
+    gantt: bool,
+
+    /// List a representative week's jobs under each weekday in time order
+    #[arg(long = "week-agenda")]
+    week_agenda: bool,
+
+    /// In --week-agenda, list any-weekday jobs once under "Every day" instead of under each day
+    #[arg(long = "every-day-section")]
+    every_day_section: bool,
+
+    /// Expand a single field value and describe it, e.g. --explain-field minute '*/15'
+    #[arg(long = "explain-field", num_args = 2, value_names = ["FIELD", "VALUE"])]
+    explain_field: Vec<String>,
+
+    /// List the @ shorthands (@daily, ...) and the five-field schedules they expand to
+    #[arg(long = "list-specials")]
+    list_specials: bool,
+
+    /// Print the resolved terminal width, its source, and the --gantt column widths
+    #[arg(long = "render-width-probe", hide = true)]
+    render_width_probe: bool,
+
+    /// Drop entries whose command contains any substring listed in FILE (one per line)
+    #[arg(long = "exclude-file", value_name = "FILE")]
+    exclude_file: Option<String>,
+
+    /// Indentation width for nested output lines
+    #[arg(long, value_name = "N", default_value_t = 2)]
+    indent: usize,
+}
+
+/// When to draw bars and separators with Unicode glyphs.
+#[derive(Clone, Copy, ValueEnum)]
+enum UnicodeMode {
+    /// Use Unicode if $LC_ALL / $LC_CTYPE / $LANG names a UTF-8 locale
+    Auto,
+    Always,
+    Never,
+}
+
+/// Placement of the numeric count in a histogram row.
+#[derive(Clone, Copy, ValueEnum)]
+enum CountPosition {
+    /// `label │ count bar`
+    Before,
+    /// `label │ bar count`
+    After,
+}
+
+/// Whether the locale environment advertises UTF-8. Follows POSIX precedence:
+/// the first non-empty of LC_ALL, LC_CTYPE, LANG decides.
+fn locale_is_utf8(var: impl Fn(&str) -> Option<String>) -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| var(name).filter(|v| !v.is_empty()))
+        .next()
+        .is_some_and(|v| {
+            let v = v.to_lowercase();
+            v.contains("utf-8") || v.contains("utf8")
+        })
+}
+
+/// Glyphs and row layout used by the text charts and trees.
+struct ChartStyle {
+    bar: &'static str,
+    sep: &'static str,
+    branch: &'static str,
+    last_branch: &'static str,
+    dash: &'static str,
+    dot: &'static str,
+    spark: [&'static str; 8],
+    count_position: CountPosition,
+    rtl: bool,
+    locale: Option<Locale>,
+}
+
+impl ChartStyle {
+    const UNICODE: ChartStyle = ChartStyle {
+        bar: "█",
+        sep: "│",
+        branch: "├─",
+        last_branch: "└─",
+        dash: "—",
+        dot: "·",
+        spark: ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"],
+        count_position: CountPosition::Before,
+        rtl: false,
+        locale: None,
+    };
+    const ASCII: ChartStyle = ChartStyle {
+        bar: "#",
+        sep: "|",
+        branch: "|-",
+        last_branch: "`-",
+        dash: "-",
+        dot: ".",
+        spark: ["_", "-", "=", "+", "*", "#", "%", "@"],
+        count_position: CountPosition::Before,
+        rtl: false,
+        locale: None,
+    };
+
+    fn new(
+        mode: UnicodeMode,
+        count_position: CountPosition,
+        rtl: bool,
+        locale: Option<Locale>,
+    ) -> ChartStyle {
+        let unicode = match mode {
+            UnicodeMode::Always => true,
+            UnicodeMode::Never => false,
+            UnicodeMode::Auto => locale_is_utf8(|name| std::env::var(name).ok()),
+        };
+        let glyphs = if unicode { Self::UNICODE } else { Self::ASCII };
+        ChartStyle {
+            count_position,
+            rtl,
+            locale,
+            ..glyphs
+        }
+    }
+
+    /// A count as printed in charts, digit-grouped when a locale is set.
+    fn count(&self, n: usize) -> String {
+        match &self.locale {
+            Some(locale) => n.to_formatted_string(locale),
+            None => n.to_string(),
+        }
+    }
+
+    /// One character per bucket, scaled to the largest bucket; empty buckets show a dot.
+    fn sparkline(&self, counts: &[usize]) -> String {
+        let max = counts.iter().copied().max().unwrap_or(0);
+        counts
+            .iter()
+            .map(|&c| {
+                if c == 0 {
+                    self.dot
+                } else {
+                    self.spark[(c * self.spark.len()).div_ceil(max) - 1]
+                }
+            })
+            .collect()
+    }
+
+    /// One histogram row: right-aligned label, separator, then count and bar in
+    /// the configured order. Right-to-left rows mirror this, with bars growing
+    /// leftward from the separator, so `max` (the chart's largest count) sets
+    /// the bar column width.
+    fn row(&self, label: &str, width: usize, count: usize, max: usize) -> String {
+        let (bar, sep) = (self.bar.repeat(count), self.sep);
+        let count = self.count(count);
+        match (self.rtl, self.count_position) {
+            (false, CountPosition::Before) => {
+                format!("{:>w$} {} {:<4} {}", label, sep, count, bar, w = width)
+            }
+            (false, CountPosition::After) => {
+                format!("{:>w$} {} {} {}", label, sep, bar, count, w = width)
+            }
+            (true, CountPosition::Before) => {
+                format!("{:>m$} {:>4} {} {:>w$}", bar, count, sep, label, m = max, w = width)
+            }
+            (true, CountPosition::After) => {
+                format!("{:>4} {:>m$} {} {:>w$}", count, bar, sep, label, m = max, w = width)
+            }
+        }
+    }
+}
+
+/// Append `more` to `bytes`, first ending `bytes` with a newline if it lacks one so
+/// the last line of the file and the first piped line stay separate entries.
+fn append_input(mut bytes: Vec<u8>, more: &[u8]) -> Vec<u8> {
+    if !bytes.is_empty() && !bytes.ends_with(b"\n") {
+        bytes.push(b'\n');
+    }
+    bytes.extend_from_slice(more);
+    bytes
+}
+
+/// Parse a --locale code such as "en" or "de" into a digit-grouping locale.
+fn parse_locale(code: &str) -> Result<Locale, String> {
+    Locale::from_name(code).map_err(|_| format!("unknown locale `{}`", code))
+}
+
+/// The 0-based position of the 1-based `--index n` among `len` entries.
+fn entry_index(n: usize, len: usize) -> Result<usize, String> {
+    if n == 0 || n > len {
+        return Err(format!("--index {} is out of range (1–{})", n, len));
+    }
+    Ok(n - 1)
+}
+
+/// Pick `n` distinct indices below `len` (all of them if `n >= len`), in ascending
+/// order. Uses a partial Fisher–Yates shuffle driven by splitmix64, so a given seed
+/// always yields the same subset.
+fn sample_indices(len: usize, n: usize, seed: u64) -> Vec<usize> {
+    let mut state = seed;
+    let mut next = || {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    };
+    let mut pool: Vec<usize> = (0..len).collect();
+    let n = n.min(len);
+    for i in 0..n {
+        let j = i + (next() % (len - i) as u64) as usize;
+        pool.swap(i, j);
+    }
+    pool.truncate(n);
+    pool.sort_unstable();
+    pool
+}
+
+/// Character encoding used to decode crontab bytes.
+#[derive(Clone, Copy, ValueEnum)]
+enum Encoding {
+    /// UTF-8; invalid bytes become U+FFFD
+    Utf8,
+    /// ISO-8859-1 / Windows-1252
+    Latin1,
+}
+
+/// Decode raw crontab bytes using the requested encoding.
+fn decode_bytes(bytes: &[u8], encoding: Encoding) -> String {
+    match encoding {
+        Encoding::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+        Encoding::Latin1 => encoding_rs::WINDOWS_1252
+            .decode_without_bom_handling(bytes)
+            .0
+            .into_owned(),
+    }
+}
+
+/// Where each canonical schedule field (minute, hour, dom, month, dow) sits in the input.
+#[derive(Clone, Copy)]
+struct FieldOrder([usize; 5]);
+
+impl FieldOrder {
+    /// Rewrite a line's leading five fields into canonical cron order, keeping the command as written.
+    fn to_canonical(self, line: &str) -> String {
+        match split_fields(line, 5) {
+            Some((fields, rest)) => {
+                let ordered: Vec<&str> = self.0.iter().map(|&i| fields[i]).collect();
+                format!("{} {}", ordered.join(" "), rest)
+            }
+            None => line.to_string(),
+        }
+    }
 }
 
+/// Parse a --field-order spec like "m,h,dow,dom,mon" naming each of the five fields once.
+fn parse_field_order(spec: &str) -> Result<FieldOrder, String> {
+    let mut order = [usize::MAX; 5];
+    let names: Vec<&str> = spec.split(',').map(str::trim).collect();
+    if names.len() != 5 {
+        return Err(format!("expected 5 comma-separated fields, got {}", names.len()));
+    }
+    for (pos, name) in names.iter().enumerate() {
+        let field = match name.to_lowercase().as_str() {
+            "m" | "min" | "minute" => 0,
+            "h" | "hour" => 1,
+            "dom" => 2,
+            "mon" | "month" => 3,
+            "dow" => 4,
+            _ => return Err(format!("unknown field `{}` (use m, h, dom, mon, dow)", name)),
+        };
+        if order[field] != usize::MAX {
+            return Err(format!("field `{}` listed twice", name));
+        }
+        order[field] = pos;
+    }
+    Ok(FieldOrder(order))
+}
+
+/// The `@` shorthands understood by `normalize_special_entry`, with their five-field
+/// expansions. `@reboot` is recognized separately and skipped.
+const SPECIALS: [(&str, &str); 6] = [
+    ("@hourly", "0 * * * *"),
+    ("@daily", "0 0 * * *"),
+    ("@weekly", "0 0 * * 0"),
+    ("@monthly", "0 0 1 * *"),
+    ("@yearly", "0 0 1 1 *"),
+    ("@annually", "0 0 1 1 *"),
+];
+
 /// Normalize “@hourly”, “@daily”, etc., into five-field cron syntax; skip “@reboot”.
 fn normalize_special_entry(line: &str) -> Option<String> {
-    let mut parts = line.split_whitespace();
-    let first = parts.next()?;
-    let rest = parts.collect::<Vec<_>>().join(" ");
-    let cron = match first {
-        "@hourly"    => "0 * * * *",
-        "@daily"     => "0 0 * * *",
-        "@weekly"    => "0 0 * * 0",
-        "@monthly"   => "0 0 1 * *",
-        "@yearly" | "@annually" => "0 0 1 1 *",
-        "@reboot"    => return None,
-        _            => return None,
-    };
+    let (first, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let rest = rest.trim_start();
+    let (_, cron) = SPECIALS.iter().find(|(name, _)| *name == first)?;
     Some(format!("{} {}", cron, rest))
 }
 
+/// Lines of `--list-specials`: each shorthand with its expansion, then `@reboot`.
+fn specials_lines() -> Vec<String> {
+    SPECIALS
+        .iter()
+        .map(|(name, cron)| format!("{:<10} {}", name, cron))
+        .chain([format!("{:<10} (runs once at startup; skipped)", "@reboot")])
+        .collect()
+}
+
 fn main() -> io::Result<()> {
     let args = Args::parse();
 
+    if let [field, value] = args.explain_field.as_slice() {
+        match explain_field(field, value) {
+            Some(line) => println!("{}", line),
+            None => {
+                eprintln!("Unknown field: {} (expected minute, hour, dom, month, or dow)", field);
+                process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
+    if args.list_specials {
+        for line in specials_lines() {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
+    if args.render_width_probe {
+        let style = ChartStyle::new(args.unicode, args.count_position, args.rtl, args.locale);
+        let (width, source) = terminal_width_and_source(|name| std::env::var(name).ok());
+        let cells = gantt_cells(width);
+        println!("terminal width: {} (from {})", width, source);
+        println!("label column:   {}", GANTT_LABEL_W);
+        println!("bar columns:    {} (+2 borders)", cells);
+        println!(
+            "{:GANTT_LABEL_W$} {}{}{}",
+            "sample",
+            style.sep,
+            style.bar.repeat(cells),
+            style.sep
+        );
+        return Ok(());
+    }
+
     // Load raw crontab (from file or `crontab -l`)
     let raw = if let Some(path) = args.file.as_deref() {
-        fs::read_to_string(path)?
+        let mut bytes = fs::read(path)?;
+        if args.also_stdin {
+            let mut piped = Vec::new();
+            io::stdin().read_to_end(&mut piped)?;
+            bytes = append_input(bytes, &piped);
+        }
+        decode_bytes(&bytes, args.encoding)
     } else {
         let output = Command::new("crontab")
             .arg("-l")
             .output()
             .expect("Failed to run `crontab -l`");
-        String::from_utf8_lossy(&output.stdout).into_owned()
+        decode_bytes(&output.stdout, args.encoding)
+    };
+
+    // --strip-comments passes the crontab through untouched except for comment lines
+    if args.strip_comments {
+        print!("{}", strip_comment_lines(&raw));
+        return Ok(());
+    }
+
+    let raw = if args.continuations {
+        join_continuations(&raw)
+    } else {
+        raw
+    };
+
+    // Strip blanks/comments/unparsable lines, normalize @special, count what was dropped
+    let mut dropped = DropCounts::default();
+    let mut normalized = normalize_lines(&raw, args.field_order, &mut dropped);
+    let base_len = normalized.len();
+
+    // Append --overlay entries after the base crontab
+    if let Some(path) = args.overlay.as_deref() {
+        let mut overlay_raw = decode_bytes(&fs::read(path)?, args.encoding);
+        if args.continuations {
+            overlay_raw = join_continuations(&overlay_raw);
+        }
+        normalized.extend(normalize_lines(&overlay_raw, args.field_order, &mut dropped));
+    }
+
+    // Substrings from --exclude-file; blank and comment lines are ignored
+    let excludes = match args.exclude_file.as_deref() {
+        Some(path) => parse_exclude_list(&fs::read_to_string(path)?),
+        None => Vec::new(),
+    };
+
+    // Apply --filter and --exclude-file, remembering which survivors came from the overlay
+    let (mut filtered, mut overlaid) =
+        filter_entries(normalized, base_len, &args.filter, &excludes);
+
+    // Narrow to a single entry with --index
+    if let Some(n) = args.index {
+        let i = match entry_index(n, filtered.len()) {
+            Ok(i) => i,
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        };
+        filtered = vec![filtered.swap_remove(i)];
+        overlaid = vec![overlaid[i]];
+    }
+
+    // Spot-check a random subset with --sample
+    if let Some(n) = args.sample {
+        let seed = args.seed.unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| d.as_nanos() as u64)
+        });
+        let picked = sample_indices(filtered.len(), n, seed);
+        filtered = picked.iter().map(|&i| filtered[i].clone()).collect();
+        overlaid = picked.iter().map(|&i| overlaid[i]).collect();
+    }
+    let lines_ref: Vec<&str> = filtered.iter().map(|s| s.as_str()).collect();
+
+    if let Some(path) = args.svg.as_deref() {
+        write_hourly_svg(path, &lines_ref, args.tz)?;
+    }
+
+    let describe: DescribeFn = if args.guru_style {
+        cron_to_guru_style
+    } else {
+        cron_to_human_readable
     };
 
-    // Strip blanks/comments, normalize @special, count dropped
+    let style = &ChartStyle::new(args.unicode, args.count_position, args.rtl, args.locale);
+
+    // Dispatch
+    if let Some(month) = &args.chart_month_detail {
+        draw_month_detail(&lines_ref, month, style, args.indent);
+    } else if let Some(date) = args.on {
+        draw_jobs_on(&lines_ref, date, describe, style, args.tz, args.indent);
+    } else if args.gantt {
+        draw_gantt(&lines_ref, style, terminal_width());
+    } else if args.week_agenda {
+        draw_week_agenda(&lines_ref, args.every_day_section, args.indent);
+    } else if args.describe_offset {
+        draw_minute_offsets(&lines_ref, style);
+    } else if args.compact_charts {
+        draw_compact_charts(&lines_ref, style, args.tz);
+    } else if args.dashline {
+        // @reboot lines pass the same filters as jobs; --index and --sample pick jobs only
+        let reboots = if args.index.is_some() || args.sample.is_some() {
+            0
+        } else {
+            dropped
+                .reboots
+                .iter()
+                .filter(|l| {
+                    let command = l.split_once(char::is_whitespace).map_or("", |(_, c)| c);
+                    keep_entry(l, command.trim_start(), &args.filter, &excludes)
+                })
+                .count()
+        };
+        println!("{}", dashline(&lines_ref, reboots, style, args.tz));
+    } else if args.chart {
+        draw_hourly_histogram(&lines_ref, style, args.tz);
+    } else if args.chart_dow {
+        draw_dow_histogram(&lines_ref, style);
+    } else if args.chart_month {
+        draw_month_histogram(&lines_ref, style);
+    } else if let Some(template) = &args.template {
+        if let Err(e) = print_templated(&lines_ref, template, describe) {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    } else if args.validate {
+        let problems = validate_entries(&lines_ref);
+        for p in &problems {
+            println!("{}", p);
+        }
+        if problems.is_empty() {
+            println!("No problems found.");
+        } else {
+            process::exit(1);
+        }
+    } else if args.check_mailto {
+        let warnings = check_mailto(&raw, &lines_ref);
+        for w in &warnings {
+            println!("{}", w);
+        }
+        if warnings.is_empty() {
+            println!("MAILTO looks fine.");
+        } else {
+            process::exit(1);
+        }
+    } else if let Some(cmd) = &args.validator_cmd {
+        let violations = run_validator(&lines_ref, |entry| run_shell_validator(cmd, entry))?;
+        for v in &violations {
+            println!("{}", v);
+        }
+        if violations.is_empty() {
+            println!("All entries passed `{}`.", cmd);
+        } else {
+            process::exit(1);
+        }
+    } else if args.semantic_duplicates {
+        report_semantic_duplicates(&lines_ref, args.indent);
+    } else if args.describe_all {
+        print_descriptions(&lines_ref, describe);
+    } else if args.tidy {
+        print_tidy(&lines_ref);
+    } else if args.at_export {
+        for line in at_export_lines(&lines_ref, Local::now().naive_local()) {
+            println!("{}", line);
+        }
+    } else if args.by_frequency {
+        draw_by_frequency(&lines_ref, describe, style, args.indent);
+    } else if args.tree {
+        draw_command_tree(&lines_ref, describe, style, args.indent);
+    } else {
+        let mut out = StandardStream::stdout(ColorChoice::Always);
+        pretty_print(
+            &mut out,
+            &lines_ref,
+            &overlaid,
+            describe,
+            args.annotate,
+            args.count_duplicates,
+        )?;
+    }
+
+    eprintln!(
+        "({} comments, {} blanks, {} unparsable dropped; {} cron jobs parsed)",
+        dropped.comments,
+        dropped.blanks,
+        dropped.unparsable,
+        lines_ref.len()
+    );
+    Ok(())
+}
+
+/// Drop lines whose first non-blank character is `#`, keeping every other line
+/// (blank lines, environment assignments, @specials) byte-for-byte, including its
+/// line ending and a missing final newline.
+fn strip_comment_lines(raw: &str) -> String {
+    raw.split_inclusive('\n')
+        .filter(|l| !l.trim_start().starts_with('#'))
+        .collect()
+}
+
+/// Join each line ending in `\` with the next one, removing the backslash-newline
+/// pair as a shell would.
+fn join_continuations(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    for line in raw.lines() {
+        match line.strip_suffix('\\') {
+            Some(head) => out.push_str(head),
+            None => {
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+/// Command substrings listed in an `--exclude-file`, one per line; blank lines and
+/// `#` comments are ignored.
+fn parse_exclude_list(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(String::from)
+        .collect()
+}
+
+/// Whether an entry passes `--filter` (no patterns, or any pattern occurs in the whole
+/// line) and `--exclude-file` (no pattern occurs in its command).
+fn keep_entry(line: &str, command: &str, filters: &[String], excludes: &[String]) -> bool {
+    (filters.is_empty() || filters.iter().any(|pat| line.contains(pat.as_str())))
+        && !excludes.iter().any(|pat| command.contains(pat.as_str()))
+}
+
+/// Apply `--filter`/`--exclude-file` to normalized entries, pairing each survivor with
+/// whether it came from the overlay (index `base_len` onwards). Each entry is tested
+/// once against all patterns, so overlapping filters never duplicate it.
+fn filter_entries(
+    entries: Vec<String>,
+    base_len: usize,
+    filters: &[String],
+    excludes: &[String],
+) -> (Vec<String>, Vec<bool>) {
+    entries
+        .into_iter()
+        .enumerate()
+        .filter(|(_, l)| {
+            let command = split_fields(l, 5).map_or("", |(_, c)| c);
+            keep_entry(l, command, filters, excludes)
+        })
+        .map(|(i, l)| (l, i >= base_len))
+        .unzip()
+}
+
+/// Lines removed while normalizing a crontab, by reason.
+#[derive(Default)]
+struct DropCounts {
+    comments: usize,
+    blanks: usize,
+    /// Lines without five schedule fields and a command, e.g. `@reboot` or `VAR=value`.
+    unparsable: usize,
+    /// `@reboot` lines, also counted in `unparsable`; kept so they can be filtered.
+    reboots: Vec<String>,
+}
+
+/// Strip blank, comment, and unparsable lines (counting them in `dropped`), expand
+/// @special entries, and bring the remaining entries' schedule fields into canonical order.
+fn normalize_lines(raw: &str, order: FieldOrder, dropped: &mut DropCounts) -> Vec<String> {
     let mut normalized = Vec::new();
-    let mut dropped = 0;
     for line in raw.lines() {
         let t = line.trim();
-        if t.is_empty() || t.starts_with('#') {
-            dropped += 1;
+        if t.is_empty() {
+            dropped.blanks += 1;
+            continue;
+        }
+        if t.starts_with('#') {
+            dropped.comments += 1;
+            continue;
+        }
+        let entry = if let Some(expanded) = normalize_special_entry(t) {
+            expanded
+        } else if t.starts_with('@') {
+            if t.starts_with("@reboot") {
+                dropped.reboots.push(t.to_string());
+            }
+            dropped.unparsable += 1;
+            continue;
+        } else {
+            order.to_canonical(t)
+        };
+        match split_fields(&entry, 5) {
+            Some((_, command)) if !command.is_empty() => normalized.push(entry),
+            _ => dropped.unparsable += 1,
+        }
+    }
+    normalized
+}
+
+/// The local hour in the given zone of a UTC hour:minute, wrapping around midnight.
+/// The minute matters for offsets that are not whole hours (e.g. +05:30).
+fn shift_hour(hour: u8, minute: u8, tz: FixedOffset) -> u8 {
+    let secs = hour as i32 * 3600 + minute as i32 * 60 + tz.local_minus_utc();
+    (secs.rem_euclid(86_400) / 3600) as u8
+}
+
+/// Count jobs per hour (0–23) plus the number with a wildcard hour.
+/// With `tz`, hours are treated as UTC and each job is bucketed by the local hours in
+/// that zone at which it fires.
+fn hourly_counts(lines: &[&str], tz: Option<FixedOffset>) -> (BTreeMap<u8, usize>, usize) {
+    let mut counts: BTreeMap<u8, usize> = BTreeMap::new();
+    let mut wildcard = 0;
+
+    for &l in lines {
+        let cols: Vec<&str> = l.split_whitespace().collect();
+        if cols.len() < 6 {
+            continue;
+        }
+        let field = cols[1];
+        if field == "*" {
+            wildcard += 1;
+        } else {
+            let minutes = expand_field(cols[0], 0, 59);
+            let mut local: BTreeSet<u8> = BTreeSet::new();
+            for h in expand_field(field, 0, 23) {
+                match tz {
+                    Some(tz) => local.extend(minutes.iter().map(|&m| shift_hour(h, m, tz))),
+                    None => {
+                        local.insert(h);
+                    }
+                }
+            }
+            for h in local {
+                *counts.entry(h).or_default() += 1;
+            }
+        }
+    }
+    (counts, wildcard)
+}
+
+/// Histogram of cron jobs per hour (0–23), wildcard 'any' first.
+fn draw_hourly_histogram(lines: &[&str], style: &ChartStyle, tz: Option<FixedOffset>) {
+    let (counts, wildcard) = hourly_counts(lines, tz);
+
+    match tz {
+        Some(tz) => println!("\n hourly distribution of cron jobs (UTC{})\n", tz),
+        None => println!("\n hourly distribution of cron jobs\n"),
+    }
+    let max = counts.values().copied().max().unwrap_or(0).max(wildcard);
+    if wildcard > 0 {
+        println!("{}", style.row("any", 3, wildcard, max));
+    }
+    for (h, &c) in &counts {
+        println!("{}", style.row(&format!("{:02}", h), 3, c, max));
+    }
+    println!();
+}
+
+/// Histogram of jobs by minute past the hour, for jobs whose minute field is a single
+/// value; jobs with lists, ranges, steps, or `*` are counted under "varies".
+fn draw_minute_offsets(lines: &[&str], style: &ChartStyle) {
+    let (counts, varies) = minute_offset_counts(lines);
+
+    println!("\n minute-past-the-hour distribution of cron jobs\n");
+    let max = counts.values().copied().max().unwrap_or(0).max(varies);
+    for (m, &c) in &counts {
+        println!("{}", style.row(&format!(":{:02}", m), 6, c, max));
+    }
+    if varies > 0 {
+        println!("{}", style.row("varies", 6, varies, max));
+    }
+    println!();
+}
+
+/// Jobs per fixed minute past the hour, plus the number whose minute field varies.
+fn minute_offset_counts(lines: &[&str]) -> (BTreeMap<u8, usize>, usize) {
+    let mut counts: BTreeMap<u8, usize> = BTreeMap::new();
+    let mut varies = 0;
+    for &l in lines {
+        let cols: Vec<&str> = l.split_whitespace().collect();
+        if cols.len() < 6 {
+            continue;
+        }
+        match cols[0].parse::<u8>() {
+            Ok(m) if m < 60 => *counts.entry(m).or_default() += 1,
+            _ => varies += 1,
+        }
+    }
+    (counts, varies)
+}
+
+/// Single-line summary for status bars: jobs, every-minute jobs, `@reboot` entries and
+/// a 24-cell hourly sparkline, joined by ` | `.
+fn dashline(
+    lines: &[&str],
+    reboots: usize,
+    style: &ChartStyle,
+    tz: Option<FixedOffset>,
+) -> String {
+    let every_minute = lines
+        .iter()
+        .filter(|l| split_fields(l, 5).is_some_and(|(f, _)| runs_every_minute(f[0], f[1])))
+        .count();
+    let (hours, any_hour) = hourly_counts(lines, tz);
+    let hourly: Vec<usize> = (0..24u8)
+        .map(|h| hours.get(&h).copied().unwrap_or(0) + any_hour)
+        .collect();
+    format!(
+        "{} jobs | {} every-minute | {} @reboot | {}",
+        style.count(lines.len()),
+        style.count(every_minute),
+        style.count(reboots),
+        style.sparkline(&hourly)
+    )
+}
+
+/// Hourly, weekday, and monthly distributions as one sparkline each. Wildcard jobs
+/// count toward every bucket of their dimension.
+fn draw_compact_charts(lines: &[&str], style: &ChartStyle, tz: Option<FixedOffset>) {
+    for line in compact_chart_lines(lines, style, tz) {
+        println!("{}", line);
+    }
+}
+
+/// Lines of `--compact-charts`: 24 hourly, 7 weekday and 12 monthly sparkline cells.
+fn compact_chart_lines(
+    lines: &[&str],
+    style: &ChartStyle,
+    tz: Option<FixedOffset>,
+) -> Vec<String> {
+    let (hours, any_hour) = hourly_counts(lines, tz);
+    let hourly: Vec<usize> = (0..24u8)
+        .map(|h| hours.get(&h).copied().unwrap_or(0) + any_hour)
+        .collect();
+    let (days, any_day) = dow_counts(lines);
+    let weekly: Vec<usize> = days.iter().map(|&c| c + any_day).collect();
+    let (months, any_month) = month_counts(lines);
+    let monthly: Vec<usize> = (1..=12u8)
+        .map(|m| months.get(&m).copied().unwrap_or(0) + any_month)
+        .collect();
+
+    vec![
+        format!("hour  {} {} 00-23", style.sparkline(&hourly), style.sep),
+        format!("dow   {} {} Sun-Sat", style.sparkline(&weekly), style.sep),
+        format!("month {} {} Jan-Dec", style.sparkline(&monthly), style.sep),
+    ]
+}
+
+/// Write the hourly histogram as a standalone SVG bar chart to `path`.
+fn write_hourly_svg(path: &str, lines: &[&str], tz: Option<FixedOffset>) -> io::Result<()> {
+    fs::write(path, hourly_svg(lines, tz))
+}
+
+/// The hourly histogram as a standalone SVG document, one bar per non-empty hour.
+fn hourly_svg(lines: &[&str], tz: Option<FixedOffset>) -> String {
+    const BAR_W: usize = 20;
+    const GAP: usize = 6;
+    const PLOT_H: usize = 200;
+    const MARGIN: usize = 40;
+
+    let (counts, wildcard) = hourly_counts(lines, tz);
+    let max = counts.values().copied().max().unwrap_or(0).max(1);
+    let width = MARGIN * 2 + 24 * (BAR_W + GAP);
+    let height = MARGIN * 2 + PLOT_H;
+    let baseline = MARGIN + PLOT_H;
+
+    let mut svg = String::new();
+    svg.push_str(&format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"monospace\" font-size=\"11\">\n",
+        w = width,
+        h = height
+    ));
+    let title = match tz {
+        Some(tz) => format!("hourly distribution of cron jobs (UTC{})", tz),
+        None => "hourly distribution of cron jobs".to_string(),
+    };
+    svg.push_str(&format!(
+        "  <text x=\"{}\" y=\"{}\" font-size=\"14\">{}</text>\n",
+        MARGIN,
+        MARGIN / 2 + 5,
+        title
+    ));
+    if wildcard > 0 {
+        svg.push_str(&format!(
+            "  <text x=\"{}\" y=\"{}\" text-anchor=\"end\">any hour: {}</text>\n",
+            width - MARGIN,
+            MARGIN / 2 + 5,
+            wildcard
+        ));
+    }
+    svg.push_str(&format!(
+        "  <line x1=\"{m}\" y1=\"{b}\" x2=\"{x2}\" y2=\"{b}\" stroke=\"black\"/>\n",
+        m = MARGIN,
+        b = baseline,
+        x2 = width - MARGIN
+    ));
+    for h in 0..24u8 {
+        let x = MARGIN + h as usize * (BAR_W + GAP) + GAP / 2;
+        let c = counts.get(&h).copied().unwrap_or(0);
+        if c > 0 {
+            let bar_h = c * PLOT_H / max;
+            svg.push_str(&format!(
+                "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"steelblue\"><title>{:02}:00 — {} jobs</title></rect>\n",
+                x,
+                baseline - bar_h,
+                BAR_W,
+                bar_h,
+                h,
+                c
+            ));
+            svg.push_str(&format!(
+                "  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+                x + BAR_W / 2,
+                baseline - bar_h - 4,
+                c
+            ));
+        }
+        svg.push_str(&format!(
+            "  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{:02}</text>\n",
+            x + BAR_W / 2,
+            baseline + 14,
+            h
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Pretty-print each crontab entry to `out` with human-readable schedule and color.
+/// Entries flagged in `overlaid` are tagged as coming from `--overlay`;
+/// `describe` turns the five schedule fields into prose; `annotate` adds inferred tags.
+/// With `count_duplicates`, repeated entries are printed once with an "(×N)" suffix,
+/// tagged as overlay if any copy came from it.
+fn pretty_print(
+    out: &mut impl WriteColor,
+    lines: &[&str],
+    overlaid: &[bool],
+    describe: DescribeFn,
+    annotate: bool,
+    count_duplicates: bool,
+) -> io::Result<()> {
+    // Per entry key: how many copies there are and whether any came from the overlay
+    let mut multiplicity: HashMap<String, (usize, bool)> = HashMap::new();
+    if count_duplicates {
+        for (i, &line) in lines.iter().enumerate() {
+            let slot = multiplicity.entry(entry_key(line)).or_default();
+            slot.0 += 1;
+            slot.1 |= overlaid.get(i).copied().unwrap_or(false);
+        }
+    }
+    let mut seen: HashSet<String> = HashSet::new();
+
+    for (i, &line) in lines.iter().enumerate() {
+        let cols: Vec<&str> = line.split_whitespace().collect();
+        if cols.len() < 6 {
             continue;
         }
-        if let Some(expanded) = normalize_special_entry(t) {
-            normalized.push(expanded);
+        let (times, from_overlay) = if count_duplicates {
+            let key = entry_key(line);
+            if !seen.insert(key.clone()) {
+                continue;
+            }
+            multiplicity[&key]
         } else {
-            normalized.push(t.to_string());
+            (1, overlaid.get(i).copied().unwrap_or(false))
+        };
+        let (m, h, dom, mon, dow) = (cols[0], cols[1], cols[2], cols[3], cols[4]);
+        let command = cols[5..].join(" ");
+
+        out.set_color(ColorSpec::new().set_fg(Some(Color::Green)))?;
+        writeln!(out, "Schedule:   {}", describe(m, h, dom, mon, dow))?;
+        out.reset()?;
+
+        out.set_color(ColorSpec::new().set_fg(Some(Color::Magenta)))?;
+        write!(out, "Command:    {}", command)?;
+        out.reset()?;
+
+        if times > 1 {
+            out.set_color(ColorSpec::new().set_fg(Some(Color::Cyan)))?;
+            write!(out, "  (×{})", times)?;
+            out.reset()?;
+        }
+
+        if from_overlay {
+            out.set_color(ColorSpec::new().set_fg(Some(Color::Yellow)))?;
+            write!(out, "  [overlay]")?;
+            out.reset()?;
+        }
+        if annotate {
+            out.set_color(ColorSpec::new().set_dimmed(true))?;
+            write!(out, "  [{}]", job_tags(&cols).join(", "))?;
+            out.reset()?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// Whitespace-insensitive identity of an entry, used to spot repeats.
+fn entry_key(line: &str) -> String {
+    line.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Split the first `n` whitespace-separated fields off `line`, returning them with
+/// the untouched remainder (leading whitespace removed, internal spacing kept).
+fn split_fields(line: &str, n: usize) -> Option<(Vec<&str>, &str)> {
+    let mut fields = Vec::with_capacity(n);
+    let mut rest = line.trim_start();
+    for _ in 0..n {
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        if end == 0 {
+            return None;
+        }
+        fields.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+    Some((fields, rest))
+}
+
+/// Lines of a shell script that schedules each job's next run after `now` as a one-off
+/// `at` job. Jobs that fire more than once a day are listed as comments instead, since
+/// a single `at` run cannot stand in for them.
+fn at_export_lines(lines: &[&str], now: NaiveDateTime) -> Vec<String> {
+    let mut out = vec!["#!/bin/sh".to_string()];
+    for &l in lines {
+        let Some((f, command)) = split_fields(l, 5) else {
+            continue;
+        };
+        let schedule = Schedule::parse(f[0], f[1], f[2], f[3], f[4]);
+        let per_day = schedule.hours.len() * schedule.minutes.len();
+        if per_day > 1 {
+            out.push(format!(
+                "# skipped (runs {} times a day, unsuitable for one-off at): {}",
+                per_day, command
+            ));
+            continue;
+        }
+        out.push(match schedule.next_after(now) {
+            Some(t) => format!(
+                "echo '{}' | at -t {}",
+                command.replace('\'', "'\\''"),
+                t.format("%Y%m%d%H%M")
+            ),
+            None => format!("# skipped (no run within a year): {}", command),
+        });
+    }
+    out
+}
+
+/// Render each entry through `template`, resolving placeholders per entry. Fails on the
+/// first unknown placeholder before anything is printed.
+fn print_templated(lines: &[&str], template: &str, describe: DescribeFn) -> Result<(), String> {
+    const PLACEHOLDERS: [&str; 9] = [
+        "minute", "hour", "dom", "month", "dow", "command", "human", "next", "freq",
+    ];
+    render_template(template, |name| PLACEHOLDERS.contains(&name).then(String::new))?;
+
+    let now = Local::now().naive_local();
+    for &l in lines {
+        let Some((f, command)) = split_fields(l, 5) else {
+            continue;
+        };
+        let rendered = render_template(template, |name| {
+            Some(match name {
+                "minute" => f[0].to_string(),
+                "hour" => f[1].to_string(),
+                "dom" => f[2].to_string(),
+                "month" => f[3].to_string(),
+                "dow" => f[4].to_string(),
+                "command" => command.to_string(),
+                "human" => describe(f[0], f[1], f[2], f[3], f[4]),
+                "next" => Schedule::parse(f[0], f[1], f[2], f[3], f[4])
+                    .next_after(now)
+                    .map_or("never".to_string(), |t| t.format("%Y-%m-%d %H:%M").to_string()),
+                "freq" => classify_frequency(f[0], f[1], f[2], f[3], f[4])
+                    .label()
+                    .to_string(),
+                _ => return None,
+            })
+        })?;
+        println!("{}", rendered);
+    }
+    Ok(())
+}
+
+/// Replace each `{name}` in `template` with `value(name)`; `None` means the placeholder
+/// is unknown and is reported as an error, as is an unclosed `{`.
+fn render_template(
+    template: &str,
+    value: impl Fn(&str) -> Option<String>,
+) -> Result<String, String> {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find('}') else {
+            return Err(format!("Unclosed `{{` in template: {}", template));
+        };
+        let name = &rest[open + 1..open + close];
+        match value(name) {
+            Some(v) => out.push_str(&v),
+            None => return Err(format!("Unknown template placeholder: {{{}}}", name)),
         }
+        rest = &rest[open + close + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Diagnostics for fields that are almost certainly mistakes: ranges whose start is
+/// after their end, and lists with more elements than the field has values. Weekday
+/// ranges are exempt from the first check since they wrap around the week on purpose.
+fn validate_entries(lines: &[&str]) -> Vec<String> {
+    const FIELDS: [(&str, usize); 5] = [
+        ("minute", 60),
+        ("hour", 24),
+        ("day-of-month", 31),
+        ("month", 12),
+        ("day-of-week", 7),
+    ];
+    let mut problems = Vec::new();
+    for (n, &l) in lines.iter().enumerate() {
+        let Some((fields, _)) = split_fields(l, 5) else {
+            continue;
+        };
+        for (i, (&field, &(name, size))) in fields.iter().zip(FIELDS.iter()).enumerate() {
+            let parts: Vec<&str> = field.split(',').collect();
+            if parts.len() > size {
+                problems.push(format!(
+                    "entry {}: {} field `{}` lists {} elements but only {} values exist",
+                    n + 1,
+                    name,
+                    field,
+                    parts.len(),
+                    size
+                ));
+            }
+            if name == "day-of-week" {
+                continue;
+            }
+            for part in parts {
+                let range = part.split('/').next().unwrap_or(part);
+                let Some((start, end)) = range.split_once('-') else {
+                    continue;
+                };
+                let value = |v: &str| {
+                    if i == 3 {
+                        parse_month_value(v)
+                    } else {
+                        v.parse::<u8>().ok()
+                    }
+                };
+                if let (Some(s), Some(e)) = (value(start), value(end))
+                    && s > e
+                {
+                    problems.push(format!(
+                        "entry {}: {} field `{}` has reversed range {} (start after end)",
+                        n + 1,
+                        name,
+                        field,
+                        range
+                    ));
+                }
+            }
+        }
+    }
+    problems
+}
+
+/// Group entries whose expanded schedules and commands are identical, even when written
+/// differently (`1` vs `Mon`, `0-6` vs `*`), keeping only groups with more than one member.
+/// Entries with a field that expands to nothing are never grouped: all such schedules
+/// would compare equal without really running at the same times.
+fn semantic_duplicate_groups<'a>(lines: &[&'a str]) -> Vec<Vec<&'a str>> {
+    let mut groups: Vec<Vec<&str>> = Vec::new();
+    let mut index: HashMap<(Schedule, String), usize> = HashMap::new();
+    for &l in lines {
+        let Some((f, command)) = split_fields(l, 5) else {
+            continue;
+        };
+        let schedule = Schedule::parse(f[0], f[1], f[2], f[3], f[4]);
+        if schedule.has_empty_field() {
+            continue;
+        }
+        let key = (schedule, entry_key(command));
+        let slot = *index.entry(key).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[slot].push(l);
+    }
+    groups.retain(|g| g.len() > 1);
+    groups
+}
+
+/// Print each group found by `semantic_duplicate_groups`.
+fn report_semantic_duplicates(lines: &[&str], indent: usize) {
+    let groups = semantic_duplicate_groups(lines);
+    let pad = " ".repeat(indent);
+    for group in &groups {
+        println!("Semantic duplicates ({} entries):", group.len());
+        for l in group {
+            println!("{}{}", pad, l);
+        }
+    }
+    if groups.is_empty() {
+        println!("No semantic duplicates found.");
+    }
+}
+
+/// Print each entry's schedule expression and its description, one per line.
+fn print_descriptions(lines: &[&str], describe: DescribeFn) {
+    for line in description_lines(lines, describe) {
+        println!("{}", line);
     }
+}
 
-    // Apply --filter if given
-    let filtered: Vec<String> = if let Some(pat) = &args.filter {
-        normalized.into_iter().filter(|l| l.contains(pat)).collect()
-    } else {
-        normalized
-    };
-    let lines_ref: Vec<&str> = filtered.iter().map(|s| s.as_str()).collect();
+/// "EXPRESSION → description" for each entry, in input order.
+fn description_lines(lines: &[&str], describe: DescribeFn) -> Vec<String> {
+    lines
+        .iter()
+        .filter_map(|l| {
+            let cols: Vec<&str> = l.split_whitespace().collect();
+            (cols.len() >= 6).then(|| {
+                format!(
+                    "{} → {}",
+                    cols[..5].join(" "),
+                    describe(cols[0], cols[1], cols[2], cols[3], cols[4])
+                )
+            })
+        })
+        .collect()
+}
 
-    // Dispatch
-    if let Some(month) = &args.chart_month_detail {
-        draw_month_detail(&lines_ref, month);
-    } else if args.chart {
-        draw_hourly_histogram(&lines_ref);
-    } else if args.chart_dow {
-        draw_dow_histogram(&lines_ref);
-    } else if args.chart_month {
-        draw_month_histogram(&lines_ref);
-    } else {
-        pretty_print(&lines_ref);
+/// Print each entry with single spaces between schedule fields and the command
+/// exactly as written, for whitespace-stable diffs.
+fn print_tidy(lines: &[&str]) {
+    for &l in lines {
+        println!("{}", tidy_line(l));
     }
-
-    eprintln!(
-        "({} raw lines dropped, {} cron jobs parsed)",
-        dropped,
-        lines_ref.len()
-    );
-    Ok(())
 }
 
-/// Break an hour field like "0", "0-5", "*/2", "1,2,3" into individual 0–23 values.
-fn parse_hour_field(field: &str) -> Vec<u8> {
-    let mut out = Vec::new();
-    for part in field.split(',') {
-        if part == "*" {
-            continue;
-        }
-        // Range "N-M"
-        if let Some(idx) = part.find('-') {
-            if let (Ok(s), Ok(e)) = (
-                part[..idx].parse::<u8>(),
-                part[idx + 1..].parse::<u8>(),
-            ) {
-                for h in s..=e {
-                    if h < 24 {
-                        out.push(h);
-                    }
-                }
-                continue;
-            }
+/// One entry with single spaces between its schedule fields and before the command.
+fn tidy_line(line: &str) -> String {
+    match split_fields(line, 5) {
+        Some((fields, command)) if !command.is_empty() => {
+            format!("{} {}", fields.join(" "), command)
         }
-        // Step "*/S"
-        if let Some(step) = part.strip_prefix("*/").and_then(|s| s.parse::<u8>().ok()) {
-            let mut h = 0;
-            while h < 24 {
-                out.push(h);
-                h += step;
-            }
+        _ => line.to_string(),
+    }
+}
+
+/// Group jobs by executable (first command token) and print each group's schedules and args.
+fn draw_command_tree(lines: &[&str], describe: DescribeFn, style: &ChartStyle, indent: usize) {
+    for line in command_tree_lines(lines, describe, style, indent) {
+        println!("{}", line);
+    }
+}
+
+/// Lines of the `--tree` view: one header per executable with a branch per job.
+fn command_tree_lines(
+    lines: &[&str],
+    describe: DescribeFn,
+    style: &ChartStyle,
+    indent: usize,
+) -> Vec<String> {
+    let mut groups: BTreeMap<&str, Vec<(String, Frequency, String)>> = BTreeMap::new();
+    for &l in lines {
+        let cols: Vec<&str> = l.split_whitespace().collect();
+        if cols.len() < 6 {
             continue;
         }
-        // Single hour
-        if let Ok(h) = part.parse::<u8>() {
-            if h < 24 {
-                out.push(h);
-            }
+        let (m, h, dom, mon, dow) = (cols[0], cols[1], cols[2], cols[3], cols[4]);
+        groups.entry(cols[5]).or_default().push((
+            describe(m, h, dom, mon, dow),
+            classify_frequency(m, h, dom, mon, dow),
+            cols[6..].join(" "),
+        ));
+    }
+
+    let pad = " ".repeat(indent);
+    let mut out = Vec::new();
+    for (exe, jobs) in &groups {
+        out.push(format!(
+            "{} ({} {})",
+            exe,
+            jobs.len(),
+            if jobs.len() == 1 { "job" } else { "jobs" }
+        ));
+        for (i, (schedule, freq, job_args)) in jobs.iter().enumerate() {
+            let branch = if i + 1 == jobs.len() {
+                style.last_branch
+            } else {
+                style.branch
+            };
+            let job_args = if job_args.is_empty() { "(no args)" } else { job_args };
+            out.push(format!(
+                "{}{} [{}] {} {} {}",
+                pad,
+                branch,
+                freq.label(),
+                schedule,
+                style.dash,
+                job_args
+            ));
         }
     }
-    out.sort_unstable();
-    out.dedup();
     out
 }
 
-/// Histogram of cron jobs per hour (0–23), wildcard 'any' first.
-fn draw_hourly_histogram(lines: &[&str]) {
-    let mut counts: HashMap<u8, usize> = HashMap::new();
-    let mut wildcard = 0;
+/// List jobs under a header per frequency class, most frequent class first.
+fn draw_by_frequency(lines: &[&str], describe: DescribeFn, style: &ChartStyle, indent: usize) {
+    for line in by_frequency_lines(lines, describe, style, indent) {
+        println!("{}", line);
+    }
+}
 
+/// Lines of the `--by-frequency` report: a header per class, then its jobs.
+fn by_frequency_lines(
+    lines: &[&str],
+    describe: DescribeFn,
+    style: &ChartStyle,
+    indent: usize,
+) -> Vec<String> {
+    let mut groups: BTreeMap<Frequency, Vec<(String, String)>> = BTreeMap::new();
     for &l in lines {
         let cols: Vec<&str> = l.split_whitespace().collect();
         if cols.len() < 6 {
             continue;
         }
-        let field = cols[1];
-        if field == "*" {
-            wildcard += 1;
-        } else {
-            for h in parse_hour_field(field) {
-                *counts.entry(h).or_default() += 1;
-            }
+        let (m, h, dom, mon, dow) = (cols[0], cols[1], cols[2], cols[3], cols[4]);
+        groups
+            .entry(classify_frequency(m, h, dom, mon, dow))
+            .or_default()
+            .push((describe(m, h, dom, mon, dow), cols[5..].join(" ")));
+    }
+
+    let pad = " ".repeat(indent);
+    let mut out = Vec::new();
+    for (freq, jobs) in &groups {
+        out.push(format!("{} ({})", freq.label(), jobs.len()));
+        for (schedule, command) in jobs {
+            out.push(format!("{}{} {} {}", pad, schedule, style.dash, command));
         }
+        out.push(String::new());
     }
+    out
+}
 
-    println!("\n hourly distribution of cron jobs\n");
-    if wildcard > 0 {
-        println!("{:>3} │ {:<4} {}", "any", wildcard, "█".repeat(wildcard));
+/// Inferred triage tags for a parsed entry (five schedule fields followed by the command).
+fn job_tags(cols: &[&str]) -> Vec<&'static str> {
+    let (m, h, dom, mon, dow) = (cols[0], cols[1], cols[2], cols[3], cols[4]);
+    let command = &cols[5..];
+    let mut tags = vec![classify_frequency(m, h, dom, mon, dow).tag()];
+    if runs_every_minute(m, h) {
+        tags.push("every-minute");
     }
-    let mut hours: Vec<u8> = counts.keys().copied().collect();
-    hours.sort_unstable();
-    for h in hours {
-        let c = counts[&h];
-        println!("{:>3} │ {:<4} {}", format!("{:02}", h), c, "█".repeat(c));
+    if !has_output_redirect(command) {
+        tags.push("no-redirect");
     }
-    println!();
+    tags.push(if command[0].starts_with('/') {
+        "absolute-path"
+    } else {
+        "relative-path"
+    });
+    tags
 }
 
-/// Pretty-print each crontab entry with human-readable schedule and color.
-fn pretty_print(lines: &[&str]) {
-    for &line in lines {
-        let cols: Vec<&str> = line.split_whitespace().collect();
-        if cols.len() < 6 {
-            continue;
+/// True when a job fires on every minute of the hours and days it runs.
+fn runs_every_minute(minute: &str, hour: &str) -> bool {
+    minute == "*" && hour == "*"
+}
+
+/// Check every entry with an external policy `runner`, which returns `Ok(None)` when
+/// the entry passes and `Ok(Some(reason))` when it is rejected.
+fn run_validator(
+    lines: &[&str],
+    runner: impl Fn(&str) -> io::Result<Option<String>>,
+) -> io::Result<Vec<String>> {
+    let mut violations = Vec::new();
+    for (n, &l) in lines.iter().enumerate() {
+        if let Some(reason) = runner(l)? {
+            violations.push(format!("entry {}: {} ({})", n + 1, l, reason));
         }
-        let (m, h, dom, mon, dow) = (cols[0], cols[1], cols[2], cols[3], cols[4]);
-        let command = cols[5..].join(" ");
+    }
+    Ok(violations)
+}
 
-        let mut out = StandardStream::stdout(ColorChoice::Always);
-        out.set_color(ColorSpec::new().set_fg(Some(Color::Green))).unwrap();
-        writeln!(
-            &mut out,
-            "Schedule:   {}",
-            cron_to_human_readable(m, h, dom, mon, dow)
-        )
-        .unwrap();
-        out.reset().unwrap();
+/// Run `sh -c cmd` with `entry` on stdin. A non-zero exit rejects the entry, with the
+/// trimmed stderr (or the exit status, if stderr is empty) as the reason.
+fn run_shell_validator(cmd: &str, entry: &str) -> io::Result<Option<String>> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(cmd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // A validator may exit without reading stdin; that is not an error on our side
+    if let Some(mut stdin) = child.stdin.take()
+        && let Err(e) = writeln!(stdin, "{}", entry)
+        && e.kind() != io::ErrorKind::BrokenPipe
+    {
+        return Err(e);
+    }
+    let output = child.wait_with_output()?;
+    if output.status.success() {
+        return Ok(None);
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    Ok(Some(if stderr.is_empty() {
+        output.status.to_string()
+    } else {
+        stderr
+    }))
+}
 
-        out.set_color(ColorSpec::new().set_fg(Some(Color::Magenta))).unwrap();
-        writeln!(&mut out, "Command:    {}", command).unwrap();
-        out.reset().unwrap();
+/// Lint the crontab's `MAILTO=` setting against its jobs. The last assignment in
+/// the raw text wins; an empty value (`MAILTO=""`) disables mail, which is only
+/// flagged when some job leaves its output unredirected.
+fn check_mailto(raw: &str, lines: &[&str]) -> Vec<String> {
+    if lines.is_empty() {
+        return Vec::new();
+    }
+    let mailto = raw
+        .lines()
+        .rev()
+        .find_map(|l| {
+            let (name, value) = l.trim().split_once('=')?;
+            (name.trim_end() == "MAILTO").then(|| value.trim().trim_matches(['"', '\'']))
+        });
+    match mailto {
+        None => vec![format!(
+            "{} job(s) but no MAILTO= assignment; output goes to the crontab owner's local mail",
+            lines.len()
+        )],
+        Some("") => lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, l)| {
+                let (_, command) = split_fields(l, 5)?;
+                let tokens: Vec<&str> = command.split_whitespace().collect();
+                (!has_output_redirect(&tokens)).then(|| {
+                    format!(
+                        "entry {}: MAILTO is empty, so output of `{}` is discarded",
+                        i + 1,
+                        command
+                    )
+                })
+            })
+            .collect(),
+        Some(_) => Vec::new(),
     }
 }
 
-/// Histogram of cron jobs per day-of-week, expanding lists/ranges.
-fn draw_dow_histogram(lines: &[&str]) {
+/// True when the command redirects or pipes its output somewhere, so cron won't mail it.
+fn has_output_redirect(command: &[&str]) -> bool {
+    command.iter().any(|tok| tok.contains('>') || tok.contains('|'))
+}
+
+/// Coarse recurrence class of a schedule, used for grouping and tagging.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Frequency {
+    Minutely,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+    Irregular,
+}
+
+impl Frequency {
+    fn label(self) -> &'static str {
+        match self {
+            Frequency::Minutely => "Minutely",
+            Frequency::Hourly => "Hourly",
+            Frequency::Daily => "Daily",
+            Frequency::Weekly => "Weekly",
+            Frequency::Monthly => "Monthly",
+            Frequency::Yearly => "Yearly",
+            Frequency::Irregular => "Irregular",
+        }
+    }
+
+    /// Lowercase form used in inline tags.
+    fn tag(self) -> &'static str {
+        match self {
+            Frequency::Minutely => "minutely",
+            Frequency::Hourly => "hourly",
+            Frequency::Daily => "daily",
+            Frequency::Weekly => "weekly",
+            Frequency::Monthly => "monthly",
+            Frequency::Yearly => "yearly",
+            Frequency::Irregular => "irregular",
+        }
+    }
+}
+
+/// Classify a schedule by how often it recurs. Anything that is not a plain
+/// every-N-minutes, hourly, daily, weekly, monthly, or yearly pattern is `Irregular`.
+fn classify_frequency(
+    minute: &str,
+    hour: &str,
+    day_of_month: &str,
+    month: &str,
+    day_of_week: &str,
+) -> Frequency {
+    let single = |f: &str| f.parse::<u8>().is_ok();
+    let every_day = day_of_month == "*" && month == "*" && day_of_week == "*";
+
+    if hour == "*" && every_day {
+        return if single(minute) {
+            Frequency::Hourly
+        } else {
+            Frequency::Minutely
+        };
+    }
+    if !single(minute) || !single(hour) {
+        return Frequency::Irregular;
+    }
+    match (day_of_month, month, day_of_week) {
+        ("*", "*", "*") => Frequency::Daily,
+        ("*", "*", dow) if parse_dow_value(dow).is_some() => Frequency::Weekly,
+        (dom, "*", "*") if single(dom) => Frequency::Monthly,
+        (dom, mon, "*") if single(dom) && parse_month_value(mon).is_some() => Frequency::Yearly,
+        _ => Frequency::Irregular,
+    }
+}
+
+/// Count jobs per day-of-week (0 = Sunday) plus the number with a wildcard weekday.
+fn dow_counts(lines: &[&str]) -> ([usize; 7], usize) {
     let mut counts = [0usize; 7];
     let mut wildcard = 0;
 
@@ -236,52 +1672,91 @@ fn draw_dow_histogram(lines: &[&str]) {
             }
         }
     }
+    (counts, wildcard)
+}
+
+/// Histogram of cron jobs per day-of-week, expanding lists/ranges.
+fn draw_dow_histogram(lines: &[&str], style: &ChartStyle) {
+    let (counts, wildcard) = dow_counts(lines);
 
     println!("\n weekday distribution of cron jobs\n");
+    let max = counts.iter().copied().max().unwrap_or(0).max(wildcard);
     if wildcard > 0 {
-        println!("{:>9} │ {:<4} {}", "any", wildcard, "█".repeat(wildcard));
+        println!("{}", style.row("any", 9, wildcard, max));
     }
-    for day in 0..7 {
-        let c = counts[day];
+    for (day, &c) in counts.iter().enumerate() {
         if c > 0 {
-            println!(
-                "{:>9} │ {:<4} {}",
-                dow_name_num(day as u8),
-                c,
-                "█".repeat(c)
-            );
+            println!("{}", style.row(dow_name_num(day as u8), 9, c, max));
         }
     }
     println!();
 }
 
-/// Parse a DOW field like "Mon", "1", "Mon-Fri", "Tue,Thu" into 0..6.
+/// Parse a DOW field like "Mon", "1", "Mon-Fri", "Tue,Thu", "*/2", "1-5/2" into 0..6,
+/// with 7 folded into Sunday. Days listed twice (e.g. "Mon,1") are returned once.
 fn parse_dow_field(field: &str) -> Vec<u8> {
+    let number = |tok: &str| if tok == "7" { Some(7) } else { parse_dow_value(tok) };
+    let mut out: Vec<u8> = expand_named_field(field, 0, 7, number)
+        .into_iter()
+        .map(|d| d % 7)
+        .collect();
+    out.sort_unstable();
+    out.dedup();
+    out
+}
+
+/// Parse a month field like "Jun", "6", "Mar-May", "Jan,7", "*/3", "Jan-Dec/6" or
+/// "Nov-Feb" into 1..12, deduplicated.
+fn parse_month_field(field: &str) -> Vec<u8> {
+    expand_named_field(field, 1, 12, parse_month_value)
+}
+
+/// Expand a month or weekday field by turning each name into its number and handing
+/// the part to `expand_field`. A range whose start comes after its end ("Nov-Feb",
+/// "Fri-Mon") wraps around past `hi` back to `lo`.
+fn expand_named_field(
+    field: &str,
+    lo: u8,
+    hi: u8,
+    value: impl Fn(&str) -> Option<u8>,
+) -> Vec<u8> {
     let mut out = Vec::new();
     for part in field.split(',') {
-        if let Some(idx) = part.find('-') {
-            let start = &part[..idx];
-            let end = &part[idx + 1..];
-            if let (Some(s), Some(e)) = (parse_dow_value(start), parse_dow_value(end)) {
-                let mut cur = s;
-                loop {
-                    out.push(cur);
-                    if cur == e {
-                        break;
+        let (range, step) = match part.split_once('/') {
+            Some((r, s)) => (r, Some(s)),
+            None => (part, None),
+        };
+        let numeric = match range.split_once('-') {
+            Some((s, e)) => match (value(s), value(e)) {
+                (Some(s), Some(e)) if s > e => {
+                    if let Some(step) = step.map_or(Some(1), |st| st.parse::<usize>().ok()) {
+                        out.extend((s..=hi).chain(lo..=e).step_by(step.max(1)));
                     }
-                    cur = (cur + 1) % 7;
+                    continue;
                 }
-            }
-        } else if let Some(d) = parse_dow_value(part) {
-            out.push(d);
-        }
+                (Some(s), Some(e)) => format!("{}-{}", s, e),
+                _ => continue,
+            },
+            None if range == "*" => range.to_string(),
+            None => match value(range) {
+                Some(v) => v.to_string(),
+                None => continue,
+            },
+        };
+        let numeric = match step {
+            Some(s) => format!("{}/{}", numeric, s),
+            None => numeric,
+        };
+        out.extend(expand_field(&numeric, lo, hi));
     }
+    out.sort_unstable();
+    out.dedup();
     out
 }
 
 fn parse_dow_value(tok: &str) -> Option<u8> {
     match tok.to_lowercase().as_str() {
-        "sun" | "0" => Some(0),
+        "sun" | "0" | "7" => Some(0),
         "mon" | "1" => Some(1),
         "tue" | "2" => Some(2),
         "wed" | "3" => Some(3),
@@ -292,6 +1767,142 @@ fn parse_dow_value(tok: &str) -> Option<u8> {
     }
 }
 
+/// Parse a month token like "6", "Jun", or "June" into 1..12.
+fn parse_month_value(tok: &str) -> Option<u8> {
+    match tok.to_lowercase().as_str() {
+        "jan" | "january" => Some(1),
+        "feb" | "february" => Some(2),
+        "mar" | "march" => Some(3),
+        "apr" | "april" => Some(4),
+        "may" => Some(5),
+        "jun" | "june" => Some(6),
+        "jul" | "july" => Some(7),
+        "aug" | "august" => Some(8),
+        "sep" | "september" => Some(9),
+        "oct" | "october" => Some(10),
+        "nov" | "november" => Some(11),
+        "dec" | "december" => Some(12),
+        _ => tok.parse().ok().filter(|n| (1..=12).contains(n)),
+    }
+}
+
+/// Expand a numeric field like "*", "5", "1-5", "*/15", "10-40/10", or "1,3" into the
+/// sorted, deduplicated values it allows within `lo..=hi`.
+fn expand_field(field: &str, lo: u8, hi: u8) -> Vec<u8> {
+    let mut out = Vec::new();
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((r, s)) => match s.parse::<u8>() {
+                Ok(s) if s > 0 => (r, s),
+                _ => continue,
+            },
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (lo, hi)
+        } else if let Some((s, e)) = range.split_once('-') {
+            match (s.parse::<u8>(), e.parse::<u8>()) {
+                (Ok(s), Ok(e)) => (s, e),
+                _ => continue,
+            }
+        } else {
+            match range.parse::<u8>() {
+                // "N/S" runs from N to the top of the field
+                Ok(n) if step > 1 => (n, hi),
+                Ok(n) => (n, n),
+                Err(_) => continue,
+            }
+        };
+        out.extend((start.max(lo)..=end.min(hi)).step_by(step as usize));
+    }
+    out.sort_unstable();
+    out.dedup();
+    out
+}
+
+/// A cron schedule with every field expanded to the concrete values it allows.
+/// Two schedules compare equal when they fire at exactly the same times.
+#[derive(PartialEq, Eq, Hash)]
+struct Schedule {
+    minutes: Vec<u8>,
+    hours: Vec<u8>,
+    days_of_month: Vec<u8>,
+    months: Vec<u8>,
+    days_of_week: Vec<u8>,
+    /// Both day fields are restricted, so a day matches if either one does (Vixie cron).
+    dom_or_dow: bool,
+}
+
+impl Schedule {
+    fn parse(minute: &str, hour: &str, day_of_month: &str, month: &str, day_of_week: &str) -> Self {
+        Schedule {
+            minutes: expand_field(minute, 0, 59),
+            hours: expand_field(hour, 0, 23),
+            days_of_month: expand_field(day_of_month, 1, 31),
+            months: parse_month_field(month),
+            days_of_week: parse_dow_field(day_of_week),
+            dom_or_dow: !day_of_month.starts_with('*') && !day_of_week.starts_with('*'),
+        }
+    }
+
+    /// Whether some field allows no value at all (e.g. an unparsable token), so the
+    /// schedule can never fire.
+    fn has_empty_field(&self) -> bool {
+        [
+            &self.minutes,
+            &self.hours,
+            &self.days_of_month,
+            &self.months,
+            &self.days_of_week,
+        ]
+        .iter()
+        .any(|f| f.is_empty())
+    }
+
+    /// Whether the schedule fires at `t`.
+    fn matches(&self, t: NaiveDateTime) -> bool {
+        self.matches_day(t.date())
+            && self.minutes.contains(&(t.minute() as u8))
+            && self.hours.contains(&(t.hour() as u8))
+    }
+
+    /// Whether the schedule fires at some time on `date`. As in Vixie cron, when both
+    /// day-of-month and day-of-week are restricted a match on either one is enough.
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let dom = self.days_of_month.contains(&(date.day() as u8));
+        let dow = self
+            .days_of_week
+            .contains(&(date.weekday().num_days_from_sunday() as u8));
+        let day = if self.dom_or_dow {
+            dom || dow
+        } else {
+            dom && dow
+        };
+        day && self.months.contains(&(date.month() as u8))
+    }
+
+    /// The first firing strictly after `from`, looking ahead at most a year (plus a day
+    /// so yearly schedules are always found).
+    fn next_after(&self, from: NaiveDateTime) -> Option<NaiveDateTime> {
+        let start = from.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        for offset in 0..=366 {
+            let date = start.date() + Duration::days(offset);
+            if !self.matches_day(date) {
+                continue;
+            }
+            for &h in &self.hours {
+                for &m in &self.minutes {
+                    let t = date.and_hms_opt(h as u32, m as u32, 0)?;
+                    if t >= start {
+                        return Some(t);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
 fn dow_name_num(d: u8) -> &'static str {
     match d {
         0 => "Sunday",
@@ -305,103 +1916,297 @@ fn dow_name_num(d: u8) -> &'static str {
     }
 }
 
-/// Histogram of cron jobs per month (1–12).
-fn draw_month_histogram(lines: &[&str]) {
-    let mut counts: HashMap<String, usize> = HashMap::new();
+/// Count jobs per month (1–12) plus the number with a wildcard month.
+fn month_counts(lines: &[&str]) -> (BTreeMap<u8, usize>, usize) {
+    let mut counts: BTreeMap<u8, usize> = BTreeMap::new();
+    let mut wildcard = 0;
     for &l in lines {
         let cols: Vec<&str> = l.split_whitespace().collect();
         if cols.len() < 6 {
             continue;
         }
-        *counts.entry(cols[3].to_string()).or_default() += 1;
+        if cols[3] == "*" {
+            wildcard += 1;
+        } else {
+            for m in parse_month_field(cols[3]) {
+                *counts.entry(m).or_default() += 1;
+            }
+        }
     }
+    (counts, wildcard)
+}
+
+/// Histogram of cron jobs per month (1–12), expanding lists/ranges.
+fn draw_month_histogram(lines: &[&str], style: &ChartStyle) {
+    let (counts, wildcard) = month_counts(lines);
 
     println!("\n monthly distribution of cron jobs\n");
-    if let Some(&c) = counts.get("*") {
-        println!("{:>9} │ {:<4} {}", "any", c, "█".repeat(c));
+    let max = counts.values().copied().max().unwrap_or(0).max(wildcard);
+    if wildcard > 0 {
+        println!("{}", style.row("any", 9, wildcard, max));
+    }
+    for (month, &c) in &counts {
+        println!("{}", style.row(month_name(&month.to_string()), 9, c, max));
+    }
+    println!();
+}
+
+/// Detailed breakdown for a specific month.
+fn draw_month_detail(lines: &[&str], month_arg: &str, style: &ChartStyle, indent: usize) {
+    match month_detail_lines(lines, month_arg, style, indent) {
+        Ok(out) => out.iter().for_each(|l| println!("{}", l)),
+        Err(e) => eprintln!("{}", e),
+    }
+}
+
+/// Lines of the `--chart-month-detail` report: jobs that can run in `month_arg`, by day
+/// of month and then by hour within each day.
+fn month_detail_lines(
+    lines: &[&str],
+    month_arg: &str,
+    style: &ChartStyle,
+    indent: usize,
+) -> Result<Vec<String>, String> {
+    let Some(month_num) = parse_month_value(month_arg) else {
+        return Err(format!(
+            "Unknown month: {} (expected 1–12, a full name like March, or an abbreviation like Mar)",
+            month_arg
+        ));
+    };
+
+    let mut day_counts: BTreeMap<u8, usize> = BTreeMap::new();
+    let mut hour_by_day: BTreeMap<u8, BTreeMap<String, usize>> = BTreeMap::new();
+
+    for &l in lines {
+        let cols: Vec<&str> = l.split_whitespace().collect();
+        if cols.len() < 6 {
+            continue;
+        }
+        if cols[3] != "*" && !parse_month_field(cols[3]).contains(&month_num) {
+            continue;
+        }
+        if let Ok(dom) = cols[2].parse::<u8>() {
+            *day_counts.entry(dom).or_default() += 1;
+            let hour_label = if cols[1] == "*" {
+                "any".to_string()
+            } else {
+                format!("{:02}", cols[1].parse::<u8>().unwrap_or(0))
+            };
+            *hour_by_day
+                .entry(dom)
+                .or_default()
+                .entry(hour_label)
+                .or_default() += 1;
+        }
+    }
+
+    let mut out = vec![format!(
+        "\nDetails for {} (month {})\n",
+        month_name(&month_num.to_string()),
+        month_num
+    )];
+    out.push(" Day-of-month distribution\n".to_string());
+    let max = day_counts.values().copied().max().unwrap_or(0);
+    for (&day, &c) in &day_counts {
+        out.push(style.row(&day.to_string(), 2, c, max));
+    }
+    out.push("\n Hourly breakdown by day\n".to_string());
+    for (&day, hours) in &hour_by_day {
+        let total = day_counts.get(&day).copied().unwrap_or(0);
+        out.push(format!("Day {}: {} jobs", day, total));
+        let max = hours.values().copied().max().unwrap_or(0);
+        for (hr, &c) in hours {
+            out.push(format!("{}{}", " ".repeat(indent), style.row(hr, 3, c, max)));
+        }
+        out.push(String::new());
+    }
+    Ok(out)
+}
+
+/// Signature shared by the schedule describers: (minute, hour, dom, month, dow) → prose.
+type DescribeFn = fn(&str, &str, &str, &str, &str) -> String;
+
+/// List the jobs that fire at least once on `date`, with their firing times.
+/// With `tz`, the date and times are local to that zone and the crontab runs in UTC.
+fn draw_jobs_on(
+    lines: &[&str],
+    date: NaiveDate,
+    describe: DescribeFn,
+    style: &ChartStyle,
+    tz: Option<FixedOffset>,
+    indent: usize,
+) {
+    const MAX_TIMES: usize = 12;
+
+    match tz {
+        Some(tz) => println!("\nJobs on {} ({}, UTC{})\n", date, date.format("%A"), tz),
+        None => println!("\nJobs on {} ({})\n", date, date.format("%A")),
     }
-    for month in 1..=12 {
-        let key = month.to_string();
-        if let Some(&c) = counts.get(&key) {
+    let pad = " ".repeat(indent);
+    for &l in lines {
+        let cols: Vec<&str> = l.split_whitespace().collect();
+        if cols.len() < 6 {
+            continue;
+        }
+        let (m, h, dom, mon, dow) = (cols[0], cols[1], cols[2], cols[3], cols[4]);
+        let times = run_times_on(&Schedule::parse(m, h, dom, mon, dow), date, tz);
+        if times.is_empty() {
+            continue;
+        }
+
+        println!("{} {} {}", describe(m, h, dom, mon, dow), style.dash, cols[5..].join(" "));
+        if times.len() > MAX_TIMES {
             println!(
-                "{:>9} │ {:<4} {}",
-                month_name(&key),
-                c,
-                "█".repeat(c)
+                "{}{} ... ({} runs, last at {})",
+                pad,
+                times[..MAX_TIMES].join(", "),
+                times.len(),
+                times[times.len() - 1]
             );
+        } else {
+            println!("{}{}", pad, times.join(", "));
+        }
+    }
+    println!();
+}
+
+/// The "HH:MM" times on `date` at which `schedule` fires. With `tz`, `date` and the
+/// times are local to that zone while the schedule is read as UTC.
+fn run_times_on(schedule: &Schedule, date: NaiveDate, tz: Option<FixedOffset>) -> Vec<String> {
+    let offset = Duration::seconds(tz.map_or(0, |tz| tz.local_minus_utc()) as i64);
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap();
+    (0..24 * 60)
+        .map(|min| midnight + Duration::minutes(min))
+        .filter(|&local| schedule.matches(local - offset))
+        .map(|local| local.format("%H:%M").to_string())
+        .collect()
+}
+
+/// Terminal width from $COLUMNS, defaulting to 80.
+fn terminal_width() -> usize {
+    terminal_width_and_source(|name| std::env::var(name).ok()).0
+}
+
+/// Terminal width together with where it came from, for --render-width-probe.
+fn terminal_width_and_source(var: impl Fn(&str) -> Option<String>) -> (usize, &'static str) {
+    var("COLUMNS")
+        .and_then(|c| c.parse().ok())
+        .filter(|&w| w > 0)
+        .map_or((80, "default"), |w| (w, "$COLUMNS"))
+}
+
+/// Width of the command label column in --gantt.
+const GANTT_LABEL_W: usize = 20;
+
+/// Number of timeline cells --gantt draws for a terminal `width` columns wide.
+fn gantt_cells(width: usize) -> usize {
+    width.saturating_sub(GANTT_LABEL_W + 3).max(24)
+}
+
+/// Which of `cells` equal slices of the day contain at least one firing of `schedule`.
+fn gantt_marks(schedule: &Schedule, cells: usize) -> Vec<bool> {
+    let mut marks = vec![false; cells];
+    for &h in &schedule.hours {
+        for &m in &schedule.minutes {
+            marks[(h as usize * 60 + m as usize) * cells / (24 * 60)] = true;
+        }
+    }
+    marks
+}
+
+/// One row per job across a 24-hour day, each cell covering an equal slice of the
+/// day and marked if the job fires in it. Day-of-month, month, and weekday are ignored.
+fn draw_gantt(lines: &[&str], style: &ChartStyle, width: usize) {
+    const LABEL_W: usize = GANTT_LABEL_W;
+    let cells = gantt_cells(width);
+
+    let mut axis = vec![' '; cells + 2];
+    for h in (0..24).step_by(6) {
+        let at = h * cells / 24;
+        for (i, ch) in h.to_string().chars().enumerate() {
+            axis[at + 1 + i] = ch;
+        }
+    }
+    println!("\n{:LABEL_W$} {}", "", axis.iter().collect::<String>().trim_end());
+
+    for &l in lines {
+        let cols: Vec<&str> = l.split_whitespace().collect();
+        if cols.len() < 6 {
+            continue;
         }
+        let schedule = Schedule::parse(cols[0], cols[1], cols[2], cols[3], cols[4]);
+        let marks = gantt_marks(&schedule, cells);
+        let label: String = cols[5..].join(" ").chars().take(LABEL_W).collect();
+        let row: String = marks
+            .iter()
+            .map(|&on| if on { style.bar } else { style.dot })
+            .collect();
+        println!("{:LABEL_W$} {}{}{}", label, style.sep, row, style.sep);
     }
     println!();
 }
 
-/// Detailed breakdown for a specific month.
-fn draw_month_detail(lines: &[&str], month_arg: &str) {
-    let month_num: u8 = match month_arg.parse() {
-        Ok(n) if (1..=12).contains(&n) => n,
-        _ => match month_arg.to_lowercase().as_str() {
-            "january"   => 1,
-            "february"  => 2,
-            "march"     => 3,
-            "april"     => 4,
-            "may"       => 5,
-            "june"      => 6,
-            "july"      => 7,
-            "august"    => 8,
-            "september" => 9,
-            "october"   => 10,
-            "november"  => 11,
-            "december"  => 12,
-            _ => {
-                eprintln!("Unknown month: {}", month_arg);
-                return;
-            }
-        },
-    };
+/// Agenda for a representative week: each weekday lists the jobs whose weekday field
+/// includes it, ordered by first firing time. Day-of-month and month are ignored.
+fn draw_week_agenda(lines: &[&str], every_day_section: bool, indent: usize) {
+    for line in week_agenda_lines(lines, every_day_section, indent) {
+        println!("{}", line);
+    }
+}
 
-    let mut day_counts: BTreeMap<u8, usize> = BTreeMap::new();
-    let mut hour_by_day: BTreeMap<u8, BTreeMap<String, usize>> = BTreeMap::new();
+/// Lines of the `--week-agenda` view, one section per weekday that has jobs.
+fn week_agenda_lines(lines: &[&str], every_day_section: bool, indent: usize) -> Vec<String> {
+    // (first time as minutes since midnight, firings per day, command)
+    let mut every_day: Vec<(u16, usize, String)> = Vec::new();
+    let mut by_day: Vec<Vec<(u16, usize, String)>> = vec![Vec::new(); 7];
 
     for &l in lines {
         let cols: Vec<&str> = l.split_whitespace().collect();
         if cols.len() < 6 {
             continue;
         }
-        if cols[3] != "*" && cols[3].parse::<u8>().ok() != Some(month_num) {
+        let schedule = Schedule::parse(cols[0], cols[1], cols[2], cols[3], cols[4]);
+        let (Some(&h), Some(&m)) = (schedule.hours.first(), schedule.minutes.first()) else {
             continue;
+        };
+        let job = (
+            h as u16 * 60 + m as u16,
+            schedule.hours.len() * schedule.minutes.len(),
+            cols[5..].join(" "),
+        );
+        if every_day_section && cols[4] == "*" {
+            every_day.push(job);
+        } else {
+            for &d in &schedule.days_of_week {
+                by_day[d as usize].push(job.clone());
+            }
         }
-        if let Ok(dom) = cols[2].parse::<u8>() {
-            *day_counts.entry(dom).or_default() += 1;
-            let hour_label = if cols[1] == "*" {
-                "any".to_string()
+    }
+
+    let pad = " ".repeat(indent);
+    let mut out = vec![String::new()];
+    let mut push_jobs = |title: &str, jobs: &mut Vec<(u16, usize, String)>| {
+        if jobs.is_empty() {
+            return;
+        }
+        jobs.sort();
+        out.push(title.to_string());
+        for (first, runs, command) in jobs.iter() {
+            let more = if *runs > 1 {
+                format!(" (+{} more)", runs - 1)
             } else {
-                format!("{:02}", cols[1].parse::<u8>().unwrap_or(0))
+                String::new()
             };
-            *hour_by_day
-                .entry(dom)
-                .or_default()
-                .entry(hour_label)
-                .or_default() += 1;
+            out.push(format!("{}{:02}:{:02}{}  {}", pad, first / 60, first % 60, more, command));
         }
-    }
+        out.push(String::new());
+    };
 
-    println!(
-        "\nDetails for {} (month {})\n",
-        month_name(&month_num.to_string()),
-        month_num
-    );
-    println!(" Day-of-month distribution\n");
-    for (&day, &c) in &day_counts {
-        println!("{:>2} │ {:<4} {}", day, c, "█".repeat(c));
-    }
-    println!("\n Hourly breakdown by day\n");
-    for (&day, hours) in &hour_by_day {
-        let total = day_counts.get(&day).copied().unwrap_or(0);
-        println!("Day {}: {} jobs", day, total);
-        for (hr, &c) in hours {
-            println!("  {:>3} │ {:<4} {}", hr, c, "█".repeat(c));
-        }
-        println!();
+    push_jobs("Every day", &mut every_day);
+    for (d, jobs) in by_day.iter_mut().enumerate() {
+        push_jobs(dow_name_num(d as u8), jobs);
     }
+    out
 }
 
 /// Convert cron fields to a human-readable schedule string.
@@ -430,7 +2235,7 @@ fn cron_to_human_readable(
             "{} every month on {} and every {}",
             time_part,
             day_of_month_with_suffix(day_of_month),
-            dow_name_num(parse_dow_field(day_of_week).get(0).copied().unwrap_or(0))
+            dow_name_num(parse_dow_field(day_of_week).first().copied().unwrap_or(0))
         );
     }
     if month != "*" && day_of_month == "*" && day_of_week != "*" {
@@ -463,6 +2268,168 @@ fn cron_to_human_readable(
     desc
 }
 
+/// Convert cron fields to a crontab.guru-style description, e.g. "At 03:00 on every day-of-month".
+fn cron_to_guru_style(
+    minute: &str,
+    hour: &str,
+    day_of_month: &str,
+    month: &str,
+    day_of_week: &str,
+) -> String {
+    let is_num = |f: &str| f.parse::<u8>().is_ok();
+    let mut desc = if is_num(minute) && is_num(hour) {
+        format!(
+            "At {:02}:{:02}",
+            hour.parse::<u8>().unwrap_or(0),
+            minute.parse::<u8>().unwrap_or(0)
+        )
+    } else {
+        let mut d = format!("At {}", guru_field(minute, "minute", str::to_string));
+        if hour != "*" {
+            d.push_str(&format!(" past {}", guru_field(hour, "hour", str::to_string)));
+        }
+        d
+    };
+
+    let plain = |v: &str| v.to_string();
+    let dow_label = |v: &str| {
+        parse_dow_value(v).map_or(v.to_string(), |d| dow_name_num(d).to_string())
+    };
+    let month_label = |v: &str| {
+        parse_month_value(v).map_or(v.to_string(), |m| month_name(&m.to_string()).to_string())
+    };
+    match (day_of_month, day_of_week) {
+        ("*", "*") => desc.push_str(" on every day-of-month"),
+        (dom, "*") => desc.push_str(&format!(" on {}", guru_field(dom, "day-of-month", plain))),
+        ("*", dow) => desc.push_str(&format!(" on {}", guru_field(dow, "day-of-week", dow_label))),
+        (dom, dow) => desc.push_str(&format!(
+            " on {} and on {}",
+            guru_field(dom, "day-of-month", plain),
+            guru_field(dow, "day-of-week", dow_label)
+        )),
+    }
+    if month != "*" {
+        desc.push_str(&format!(" in {}", guru_field(month, "month", month_label)));
+    }
+    desc
+}
+
+/// Expand one field value and describe it, e.g. "`*/15` → 0, 15, 30, 45 → every 15 minutes".
+/// Returns `None` for an unknown field name.
+fn explain_field(field: &str, value: &str) -> Option<String> {
+    let plain = |v: &str| v.to_string();
+    let dow_label = |v: &str| {
+        parse_dow_value(v).map_or(v.to_string(), |d| dow_name_num(d).to_string())
+    };
+    let month_label = |v: &str| {
+        parse_month_value(v).map_or(v.to_string(), |m| month_name(&m.to_string()).to_string())
+    };
+    let (unit, values, names): (&str, Vec<u8>, Vec<String>) = match field {
+        "minute" => ("minute", expand_field(value, 0, 59), Vec::new()),
+        "hour" => ("hour", expand_field(value, 0, 23), Vec::new()),
+        "dom" => ("day-of-month", expand_field(value, 1, 31), Vec::new()),
+        "month" => {
+            let v = parse_month_field(value);
+            let n = v.iter().map(|m| month_name(&m.to_string()).to_string()).collect();
+            ("month", v, n)
+        }
+        "dow" => {
+            let v = parse_dow_field(value);
+            let n = v.iter().map(|&d| dow_name_num(d).to_string()).collect();
+            ("day-of-week", v, n)
+        }
+        _ => return None,
+    };
+
+    let listed = if values.is_empty() {
+        "(no valid values)".to_string()
+    } else if names.is_empty() {
+        values.iter().map(u8::to_string).collect::<Vec<_>>().join(", ")
+    } else {
+        names.join(", ")
+    };
+    let sentence = match value.strip_prefix("*/") {
+        Some(step) if matches!(field, "minute" | "hour") => format!("every {} {}s", step, unit),
+        _ => match field {
+            "month" => guru_field(value, unit, month_label),
+            "dow" => guru_field(value, unit, dow_label),
+            _ => guru_field(value, unit, plain),
+        },
+    };
+    Some(format!("`{}` → {} → {}", value, listed, sentence))
+}
+
+/// Describe one cron field crontab.guru-style: "every minute", "every 15th minute",
+/// "hour 1 through 5", "every 10th minute from 10 through 40", "Monday and Friday".
+/// Weekday and month values are rendered bare ("Monday"), numeric ones keep their unit
+/// ("minute 5"). Values that name the same thing (`Mon,1`) are listed once.
+fn guru_field(field: &str, unit: &str, name: impl Fn(&str) -> String) -> String {
+    if field == "*" {
+        return format!("every {}", unit);
+    }
+    if let Some(step) = field.strip_prefix("*/") {
+        return format!("every {} {}", day_of_month_with_suffix(step), unit);
+    }
+    let bare = unit == "day-of-week" || unit == "month";
+    let mut values: Vec<String> = Vec::new();
+    let mut steps: Vec<String> = Vec::new();
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((r, s)) => (r, Some(s)),
+            None => (part, None),
+        };
+        let text = match range.split_once('-') {
+            Some((s, e)) => format!("{} through {}", name(s), name(e)),
+            None if range == "*" => String::new(),
+            None => name(range),
+        };
+        match step {
+            Some(step) => {
+                let every = format!("every {} {}", day_of_month_with_suffix(step), unit);
+                let phrase = match (text.is_empty(), range.contains('-')) {
+                    (true, _) => every,
+                    (false, true) => format!("{} from {}", every, text),
+                    (false, false) => format!("{} from {} through {}", every, text, unit_max(unit)),
+                };
+                if !steps.contains(&phrase) {
+                    steps.push(phrase);
+                }
+            }
+            None => {
+                if !values.contains(&text) {
+                    values.push(text);
+                }
+            }
+        }
+    }
+    let mut phrases = Vec::new();
+    if !values.is_empty() {
+        let list = join_and(&values);
+        phrases.push(if bare { list } else { format!("{} {}", unit, list) });
+    }
+    phrases.extend(steps);
+    join_and(&phrases)
+}
+
+/// Join items as "a, b and c".
+fn join_and(items: &[String]) -> String {
+    match items.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+        _ => items.join(""),
+    }
+}
+
+/// Highest value a field can take, named as in `guru_field` output.
+fn unit_max(unit: &str) -> &'static str {
+    match unit {
+        "minute" => "59",
+        "hour" => "23",
+        "day-of-month" => "31",
+        "month" => "December",
+        _ => "Saturday",
+    }
+}
+
 fn format_time(hour: &str, minute: &str) -> String {
     let hh: u8 = hour.parse().unwrap_or(0);
     let mm: u8 = minute.parse().unwrap_or(0);
@@ -527,3 +2494,614 @@ fn day_of_month_with_suffix(day: &str) -> String {
     };
     format!("{}{}", num, suffix)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An environment lookup backed by a fixed list of variables.
+    fn env<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |name| {
+            vars.iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.to_string())
+        }
+    }
+
+    #[test]
+    fn lc_all_overrides_utf8_lang() {
+        assert!(!locale_is_utf8(env(&[("LC_ALL", "C"), ("LANG", "en_US.UTF-8")])));
+    }
+
+    #[test]
+    fn empty_lc_all_falls_through_to_lang() {
+        assert!(locale_is_utf8(env(&[("LC_ALL", ""), ("LANG", "en_US.UTF-8")])));
+        assert!(!locale_is_utf8(env(&[("LC_ALL", ""), ("LANG", "C")])));
+    }
+
+    #[test]
+    fn unset_locale_selects_ascii() {
+        assert!(!locale_is_utf8(env(&[])));
+    }
+
+    #[test]
+    fn validator_collects_rejected_entries() {
+        let lines = ["0 3 * * * backup.sh", "0 2 * * * rm -rf /tmp/cache"];
+        let violations = run_validator(&lines, |entry| {
+            Ok(entry.contains("rm ").then(|| "uses rm".to_string()))
+        })
+        .unwrap();
+        assert_eq!(violations, ["entry 2: 0 2 * * * rm -rf /tmp/cache (uses rm)"]);
+    }
+
+    #[test]
+    fn hourly_counts_expand_stepped_ranges() {
+        let (counts, wildcard) = hourly_counts(&["0 8-18/2 * * * b"], None);
+        assert_eq!(wildcard, 0);
+        assert_eq!(counts.keys().copied().collect::<Vec<_>>(), [8, 10, 12, 14, 16, 18]);
+    }
+
+    #[test]
+    fn guru_style_describes_stepped_ranges() {
+        assert_eq!(
+            cron_to_guru_style("10-40/10", "*", "*", "*", "*"),
+            "At every 10th minute from 10 through 40 on every day-of-month"
+        );
+        assert_eq!(
+            cron_to_guru_style("0", "8-18/2", "*", "*", "*"),
+            "At minute 0 past every 2nd hour from 8 through 18 on every day-of-month"
+        );
+    }
+
+    #[test]
+    fn guru_style_lists_equivalent_weekdays_once() {
+        assert_eq!(cron_to_guru_style("0", "0", "*", "*", "Mon,1"), "At 00:00 on Monday");
+    }
+
+    #[test]
+    fn explain_field_every_fifteen_minutes() {
+        assert_eq!(
+            explain_field("minute", "*/15").unwrap(),
+            "`*/15` → 0, 15, 30, 45 → every 15 minutes"
+        );
+    }
+
+    #[test]
+    fn explain_field_stepped_range_matches_expansion() {
+        assert_eq!(
+            explain_field("hour", "1-5/2").unwrap(),
+            "`1-5/2` → 1, 3, 5 → every 2nd hour from 1 through 5"
+        );
+    }
+
+    #[test]
+    fn sample_is_deterministic_for_a_seed() {
+        let first = sample_indices(20, 2, 1);
+        assert_eq!(first, sample_indices(20, 2, 1));
+        assert_eq!(first, [5, 9]);
+    }
+
+    #[test]
+    fn sample_of_zero_is_empty() {
+        assert!(sample_indices(20, 0, 1).is_empty());
+    }
+
+    #[test]
+    fn sample_larger_than_input_keeps_everything_in_order() {
+        assert_eq!(sample_indices(3, 3, 7), [0, 1, 2]);
+        assert_eq!(sample_indices(3, 10, 7), [0, 1, 2]);
+        assert!(sample_indices(0, 2, 7).is_empty());
+    }
+
+    /// Hash of the (schedule, command) key `report_semantic_duplicates` groups by.
+    fn semantic_hash(line: &str) -> u64 {
+        use std::hash::{DefaultHasher, Hash, Hasher};
+        let (f, command) = split_fields(line, 5).unwrap();
+        let mut hasher = DefaultHasher::new();
+        (Schedule::parse(f[0], f[1], f[2], f[3], f[4]), entry_key(command)).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn weekday_name_and_number_are_semantic_duplicates() {
+        assert_eq!(semantic_hash("0 0 * * 1 /bin/x"), semantic_hash("0 0 * * Mon /bin/x"));
+    }
+
+    #[test]
+    fn restricted_weekday_range_is_not_a_wildcard() {
+        // 0-6 makes both day fields restricted, so cron ORs them: every day, not the 1st
+        let all_days = Schedule::parse("0", "0", "1", "*", "0-6");
+        let first_only = Schedule::parse("0", "0", "1", "*", "*");
+        assert!(all_days != first_only);
+        assert_ne!(semantic_hash("0 0 1 * 0-6 /bin/x"), semantic_hash("0 0 1 * * /bin/x"));
+    }
+
+    fn canonical_order() -> FieldOrder {
+        parse_field_order("m,h,dom,mon,dow").unwrap()
+    }
+
+    #[test]
+    fn normalize_counts_dropped_lines_by_reason() {
+        let raw = "# nightly jobs\n\nMAILTO=ops@example.com\n@reboot /bin/start\n\
+                   @daily /bin/rotate\n0 3 * * * /bin/backup\n0 3 *\n";
+        let mut dropped = DropCounts::default();
+        let jobs = normalize_lines(raw, canonical_order(), &mut dropped);
+        assert_eq!(jobs, ["0 0 * * * /bin/rotate", "0 3 * * * /bin/backup"]);
+        assert_eq!(dropped.comments, 1);
+        assert_eq!(dropped.blanks, 1);
+        // VAR=value, @reboot and the truncated entry are not cron jobs
+        assert_eq!(dropped.unparsable, 3);
+        assert_eq!(dropped.reboots, ["@reboot /bin/start"]);
+    }
+
+    #[test]
+    fn reordered_line_lands_in_canonical_fields() {
+        let order = parse_field_order("h,m,dow,mon,dom").unwrap();
+        assert_eq!(
+            order.to_canonical("3 15 Mon * 1 /bin/backup --full"),
+            "15 3 1 * Mon /bin/backup --full"
+        );
+    }
+
+    #[test]
+    fn field_order_rejects_duplicates_and_wrong_counts() {
+        assert!(parse_field_order("m,h,m,mon,dow").is_err());
+        assert!(parse_field_order("m,h,dom,mon").is_err());
+        assert!(parse_field_order("m,h,dom,mon,dow,m").is_err());
+    }
+
+    fn runs_on(line: &str, date: &str) -> Vec<String> {
+        let (f, _) = split_fields(line, 5).unwrap();
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap();
+        run_times_on(&Schedule::parse(f[0], f[1], f[2], f[3], f[4]), date, None)
+    }
+
+    #[test]
+    fn jobs_on_date_include_only_matching_days() {
+        assert_eq!(runs_on("0 0 25 12 * /usr/bin/xmas", "2024-12-25"), ["00:00"]);
+        assert!(runs_on("0 0 1 1 * /usr/bin/newyear", "2024-12-25").is_empty());
+    }
+
+    #[test]
+    fn weekday_steps_and_seven_expand() {
+        assert_eq!(parse_dow_field("*/2"), [0, 2, 4, 6]);
+        assert_eq!(parse_dow_field("1-5/2"), [1, 3, 5]);
+        assert_eq!(parse_dow_field("7"), [0]);
+        assert_eq!(parse_dow_field("Fri-Mon"), [0, 1, 5, 6]);
+        // 2026-10-18 is a Sunday
+        assert_eq!(runs_on("0 0 * * */2 x", "2026-10-18"), ["00:00"]);
+        assert_eq!(runs_on("0 0 * * 7 y", "2026-10-18"), ["00:00"]);
+        assert_eq!(runs_on("0 0 * * 1-5/2 x", "2026-10-19"), ["00:00"]);
+    }
+
+    #[test]
+    fn month_steps_and_wrapped_ranges_expand() {
+        assert_eq!(parse_month_field("1-12/3"), [1, 4, 7, 10]);
+        assert_eq!(parse_month_field("Jan-Dec/6"), [1, 7]);
+        assert_eq!(parse_month_field("Nov-Feb"), [1, 2, 11, 12]);
+        let (counts, _) = month_counts(&["0 0 * Nov-Feb * b"]);
+        assert_eq!(counts.keys().copied().collect::<Vec<_>>(), [1, 2, 11, 12]);
+    }
+
+    #[test]
+    fn weekday_listed_by_name_and_number_counts_once() {
+        let (counts, wildcard) = dow_counts(&["0 0 * * Mon,1 x"]);
+        assert_eq!(wildcard, 0);
+        assert_eq!(counts, [0, 1, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn different_stepped_schedules_are_not_duplicates() {
+        let lines = [
+            "0 0 * 1-12/3 * x",
+            "0 0 * Nov-Feb * x",
+            "0 0 * Jan-Dec/6 * x",
+            "0 0 * * */2 x",
+            "0 0 * * 1-5/2 x",
+        ];
+        assert!(semantic_duplicate_groups(&lines).is_empty());
+    }
+
+    #[test]
+    fn schedules_with_empty_fields_are_never_grouped() {
+        assert!(semantic_duplicate_groups(&["0 0 * * Foo x", "0 0 * * Bar x"]).is_empty());
+        assert_eq!(
+            semantic_duplicate_groups(&["0 0 * * 1 x", "0 0 * * Mon x"]),
+            [["0 0 * * 1 x", "0 0 * * Mon x"]]
+        );
+    }
+
+    #[test]
+    fn explain_field_month_and_weekday_steps() {
+        assert_eq!(
+            explain_field("month", "1-12/3").unwrap(),
+            "`1-12/3` → January, April, July, October → every 3rd month from January through December"
+        );
+        assert_eq!(
+            explain_field("dow", "*/2").unwrap(),
+            "`*/2` → Sunday, Tuesday, Thursday, Saturday → every 2nd day-of-week"
+        );
+    }
+
+    #[test]
+    fn tz_shifts_hourly_buckets() {
+        let plus_two = FixedOffset::east_opt(2 * 3600).unwrap();
+        let (counts, _) = hourly_counts(&["0 0 * * * x"], Some(plus_two));
+        assert_eq!(counts.keys().copied().collect::<Vec<_>>(), [2]);
+    }
+
+    #[test]
+    fn tz_with_half_hour_offset_uses_the_minute() {
+        let plus_five_thirty = FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap();
+        // 00:45 UTC is 06:15 at +05:30
+        let (counts, _) = hourly_counts(&["45 0 * * * x"], Some(plus_five_thirty));
+        assert_eq!(counts.keys().copied().collect::<Vec<_>>(), [6]);
+    }
+
+    #[test]
+    fn at_export_schedules_daily_job_and_skips_frequent_ones() {
+        let now = NaiveDate::from_ymd_opt(2026, 10, 16)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let lines = at_export_lines(&["0 3 * * * /usr/bin/backup", "0 */2 * * * poll"], now);
+        assert_eq!(
+            lines,
+            [
+                "#!/bin/sh",
+                "echo '/usr/bin/backup' | at -t 202610170300",
+                "# skipped (runs 12 times a day, unsuitable for one-off at): poll",
+            ]
+        );
+    }
+
+    #[test]
+    fn strip_comments_keeps_env_lines_and_line_endings() {
+        let raw = "# nightly jobs\r\nMAILTO=ops@x\r\n\r\n  # note\r\n@daily  rotate\r\n0 3 * * * a";
+        assert_eq!(
+            strip_comment_lines(raw),
+            "MAILTO=ops@x\r\n\r\n@daily  rotate\r\n0 3 * * * a"
+        );
+    }
+
+    /// `pretty_print` output without color codes.
+    fn pretty(lines: &[&str], overlaid: &[bool], count_duplicates: bool) -> String {
+        let mut out = termcolor::Buffer::no_color();
+        pretty_print(&mut out, lines, overlaid, cron_to_human_readable, false, count_duplicates)
+            .unwrap();
+        String::from_utf8(out.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn pretty_print_writes_one_schedule_line_per_entry() {
+        let lines: Vec<String> = (0..50).map(|i| format!("{} 3 * * * job{}", i, i)).collect();
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let text = pretty(&lines, &[], false);
+        assert_eq!(text.matches("Schedule:").count(), 50);
+    }
+
+    #[test]
+    fn duplicates_merge_with_count_and_keep_overlay_tag() {
+        let lines = ["0 3 * * * backup.sh", "0 3 * * * backup.sh", "0 4 * * * other"];
+        let text = pretty(&lines, &[false, true, false], true);
+        assert_eq!(text.matches("Schedule:").count(), 2);
+        assert!(text.contains("Command:    backup.sh  (×2)  [overlay]\n"));
+        assert!(text.contains("Command:    other\n"));
+    }
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn overlapping_filters_keep_an_entry_once() {
+        let entries = strings(&["0 3 * * * /usr/bin/backup", "0 4 * * * /bin/other"]);
+        let (kept, overlaid) =
+            filter_entries(entries, 1, &strings(&["backup", "/usr/bin"]), &[]);
+        assert_eq!(kept, ["0 3 * * * /usr/bin/backup"]);
+        assert_eq!(overlaid, [false]);
+    }
+
+    #[test]
+    fn exclude_file_patterns_drop_matching_commands() {
+        let excludes = parse_exclude_list("# noisy jobs\nlogrotate\n\n  /usr/bin/healthcheck  \n");
+        assert_eq!(excludes, ["logrotate", "/usr/bin/healthcheck"]);
+        let entries = strings(&[
+            "0 0 * * * /usr/sbin/logrotate /etc/logrotate.conf",
+            "*/5 * * * * /usr/bin/healthcheck",
+            "0 3 * * * /usr/bin/backup",
+        ]);
+        let (kept, _) = filter_entries(entries, 3, &[], &excludes);
+        assert_eq!(kept, ["0 3 * * * /usr/bin/backup"]);
+    }
+
+    #[test]
+    fn dashline_has_four_segments_and_a_day_long_sparkline() {
+        let style = ChartStyle::new(UnicodeMode::Never, CountPosition::Before, false, None);
+        let line = dashline(&["* * * * * poll", "0 3 * * * backup"], 1, &style, None);
+        let segments: Vec<&str> = line.split(" | ").collect();
+        assert_eq!(segments[..3], ["2 jobs", "1 every-minute", "1 @reboot"]);
+        assert_eq!(segments[3].chars().count(), 24);
+    }
+
+    fn ascii_style() -> ChartStyle {
+        ChartStyle::new(UnicodeMode::Never, CountPosition::Before, false, None)
+    }
+
+    #[test]
+    fn month_abbreviation_parses() {
+        assert_eq!(parse_month_value("Jun"), Some(6));
+        assert_eq!(parse_month_value("june"), Some(6));
+        assert_eq!(parse_month_value("Jux"), None);
+    }
+
+    #[test]
+    fn month_detail_keeps_only_jobs_in_that_month() {
+        let lines = ["0 3 15 Jun * summer", "0 4 20 Jul * july", "0 5 1 * * monthly"];
+        let out = month_detail_lines(&lines, "Jun", &ascii_style(), 2).unwrap();
+        assert!(out[0].contains("Details for June (month 6)"));
+        assert!(out.contains(&"Day 15: 1 jobs".to_string()));
+        assert!(out.contains(&"Day 1: 1 jobs".to_string()));
+        assert!(!out.contains(&"Day 20: 1 jobs".to_string()));
+        assert!(month_detail_lines(&lines, "Jux", &ascii_style(), 2).is_err());
+    }
+
+    #[test]
+    fn indent_sets_sub_line_prefix() {
+        let out = month_detail_lines(&["0 3 15 Jun * a"], "Jun", &ascii_style(), 4).unwrap();
+        let day = out.iter().position(|l| l == "Day 15: 1 jobs").unwrap();
+        assert_eq!(out[day + 1], format!("    {}", ascii_style().row("03", 3, 1, 1)));
+    }
+
+    #[test]
+    fn overlay_entries_are_listed_and_tagged() {
+        let entries = strings(&["0 3 * * * base.sh", "0 4 * * * new.sh"]);
+        let (kept, overlaid) = filter_entries(entries, 1, &[], &[]);
+        let kept: Vec<&str> = kept.iter().map(String::as_str).collect();
+        let text = pretty(&kept, &overlaid, false);
+        assert!(text.contains("Command:    base.sh\n"));
+        assert!(text.contains("Command:    new.sh  [overlay]\n"));
+    }
+
+    #[test]
+    fn svg_has_one_rect_per_non_empty_hour() {
+        let svg = hourly_svg(&["0 3 * * * a", "0 3 * * * b", "0 9,17 * * * c"], None);
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<rect").count(), 3);
+    }
+
+    #[test]
+    fn latin1_bytes_decode() {
+        assert_eq!(decode_bytes(b"0 3 * * * caf\xE9", Encoding::Latin1), "0 3 * * * café");
+        assert_eq!(decode_bytes(b"caf\xE9", Encoding::Utf8), "caf\u{FFFD}");
+    }
+
+    #[test]
+    fn guru_style_daily_job() {
+        assert_eq!(
+            cron_to_guru_style("0", "3", "*", "*", "*"),
+            "At 03:00 on every day-of-month"
+        );
+    }
+
+    #[test]
+    fn command_tree_groups_jobs_by_executable() {
+        let lines = [
+            "0 3 * * * /usr/bin/backup.sh --full",
+            "0 4 * * 0 /usr/bin/backup.sh --incremental",
+        ];
+        let out = command_tree_lines(&lines, cron_to_human_readable, &ascii_style(), 2);
+        assert_eq!(out.len(), 3);
+        assert_eq!(out[0], "/usr/bin/backup.sh (2 jobs)");
+        assert!(out[1].starts_with("  |- [Daily]") && out[1].ends_with("- --full"));
+        assert!(out[2].starts_with("  `- [Weekly]") && out[2].ends_with("- --incremental"));
+    }
+
+    #[test]
+    fn by_frequency_puts_jobs_under_their_class() {
+        let lines = ["0 4 * * 0 /usr/bin/weekly.sh", "0 3 * * * /usr/bin/daily.sh"];
+        let out = by_frequency_lines(&lines, cron_to_human_readable, &ascii_style(), 2);
+        let daily = out.iter().position(|l| l == "Daily (1)").unwrap();
+        let weekly = out.iter().position(|l| l == "Weekly (1)").unwrap();
+        assert!(daily < weekly);
+        assert!(out[daily + 1].ends_with("- /usr/bin/daily.sh"));
+        assert!(out[weekly + 1].ends_with("- /usr/bin/weekly.sh"));
+    }
+
+    #[test]
+    fn tidy_single_spaces_fields_and_keeps_command() {
+        assert_eq!(
+            tidy_line("0   3 *  * *   backup.sh  --flag"),
+            "0 3 * * * backup.sh  --flag"
+        );
+    }
+
+    #[test]
+    fn index_selects_the_nth_entry() {
+        let entries = ["0 1 * * * a", "0 2 * * * b", "0 3 * * * c"];
+        assert_eq!(entries[entry_index(2, entries.len()).unwrap()], "0 2 * * * b");
+    }
+
+    #[test]
+    fn index_out_of_range_is_an_error() {
+        assert_eq!(entry_index(4, 3), Err("--index 4 is out of range (1–3)".to_string()));
+        assert!(entry_index(0, 3).is_err());
+    }
+
+    #[test]
+    fn every_minute_relative_job_tags() {
+        let cols: Vec<&str> = "* * * * * poll.sh --quiet".split_whitespace().collect();
+        assert_eq!(
+            job_tags(&cols),
+            ["minutely", "every-minute", "no-redirect", "relative-path"]
+        );
+    }
+
+    #[test]
+    fn count_follows_bar_when_after() {
+        let style = ChartStyle::new(UnicodeMode::Never, CountPosition::After, false, None);
+        assert_eq!(style.row("03", 3, 2, 2), " 03 | ## 2");
+        assert_eq!(ascii_style().row("03", 3, 2, 2), " 03 | 2    ##");
+    }
+
+    #[test]
+    fn file_and_stdin_entries_are_merged() {
+        let merged = append_input(b"0 3 * * * from-file".to_vec(), b"0 4 * * * from-stdin\n");
+        let raw = decode_bytes(&merged, Encoding::Utf8);
+        let jobs = normalize_lines(&raw, canonical_order(), &mut DropCounts::default());
+        assert_eq!(jobs, ["0 3 * * * from-file", "0 4 * * * from-stdin"]);
+    }
+
+    #[test]
+    fn week_agenda_lists_monday_job_only_on_monday() {
+        let out = week_agenda_lines(&["0 9 * * Mon /usr/bin/report"], false, 2);
+        assert_eq!(out, ["", "Monday", "  09:00  /usr/bin/report", ""]);
+    }
+
+    #[test]
+    fn gantt_marks_every_six_hours() {
+        let schedule = Schedule::parse("0", "*/6", "*", "*", "*");
+        let marks = gantt_marks(&schedule, gantt_cells(80));
+        assert_eq!(marks.iter().filter(|&&on| on).count(), 4);
+    }
+
+    #[test]
+    fn describe_all_prints_expression_arrow_description() {
+        let out = description_lines(&["0 3 * * * a", "0 4 * * 0 b"], cron_to_guru_style);
+        assert_eq!(
+            out,
+            [
+                "0 3 * * * → At 03:00 on every day-of-month",
+                "0 4 * * 0 → At 04:00 on Sunday",
+            ]
+        );
+    }
+
+    #[test]
+    fn histogram_buckets_ignore_input_order() {
+        let lines = [
+            "0 3 * Jun Mon a",
+            "0 17 * Jan Fri b",
+            "0 9,3 * Mar,Jun * c",
+            "0 23 * * Sun d",
+        ];
+        let mut shuffled = lines;
+        shuffled.reverse();
+        shuffled.swap(0, 2);
+        assert_eq!(hourly_counts(&lines, None), hourly_counts(&shuffled, None));
+        assert_eq!(dow_counts(&lines), dow_counts(&shuffled));
+        assert_eq!(month_counts(&lines), month_counts(&shuffled));
+        assert_eq!(
+            dashline(&lines, 0, &ascii_style(), None),
+            dashline(&shuffled, 0, &ascii_style(), None)
+        );
+    }
+
+    #[test]
+    fn template_fills_placeholders() {
+        let value = |name: &str| match name {
+            "human" => Some(cron_to_human_readable("0", "3", "*", "*", "*")),
+            "command" => Some("/usr/bin/backup.sh --full".to_string()),
+            _ => None,
+        };
+        assert_eq!(
+            render_template("{human}: {command}", value).unwrap(),
+            "at 03:00 AM: /usr/bin/backup.sh --full"
+        );
+    }
+
+    #[test]
+    fn template_rejects_unknown_and_unclosed_placeholders() {
+        let value = |name: &str| (name == "command").then(|| "x".to_string());
+        assert_eq!(
+            render_template("{cmd}", value),
+            Err("Unknown template placeholder: {cmd}".to_string())
+        );
+        assert!(render_template("{command", value).is_err());
+    }
+
+    #[test]
+    fn rtl_row_puts_bar_first_and_right_aligns_label() {
+        let style = ChartStyle::new(UnicodeMode::Never, CountPosition::Before, true, None);
+        assert_eq!(style.row("03", 3, 2, 4), "  ##    2 |  03");
+        assert_eq!(style.row("12", 3, 4, 4), "####    4 |  12");
+    }
+
+    #[test]
+    fn reversed_hour_range_is_flagged() {
+        assert_eq!(
+            validate_entries(&["0 10-2 * * * /usr/bin/backup.sh"]),
+            ["entry 1: hour field `10-2` has reversed range 10-2 (start after end)"]
+        );
+        assert!(validate_entries(&["0 2-10 * * * /usr/bin/backup.sh"]).is_empty());
+    }
+
+    #[test]
+    fn backslash_continuation_joins_into_one_entry() {
+        let raw = "0 3 * * * /usr/bin/backup.sh \\\n  --full\n*/5 * * * * poll\n";
+        let joined = join_continuations(raw);
+        let entries: Vec<&str> = joined.lines().collect();
+        assert_eq!(entries, ["0 3 * * * /usr/bin/backup.sh   --full", "*/5 * * * * poll"]);
+    }
+
+    #[test]
+    fn missing_mailto_warns_and_explicit_mailto_is_clean() {
+        let lines = ["0 3 * * * /usr/bin/backup.sh"];
+        let warnings = check_mailto("0 3 * * * /usr/bin/backup.sh\n", &lines);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("no MAILTO= assignment"));
+        assert!(check_mailto("MAILTO=ops@x\n0 3 * * * /usr/bin/backup.sh\n", &lines).is_empty());
+    }
+
+    #[test]
+    fn compact_sparklines_have_one_cell_per_bucket() {
+        let lines = ["0 3 * * Mon a", "*/5 * 1 Jun * b"];
+        let cells: Vec<usize> = compact_chart_lines(&lines, &ascii_style(), None)
+            .iter()
+            .map(|l| l.split_whitespace().nth(1).unwrap().chars().count())
+            .collect();
+        assert_eq!(cells, [24, 7, 12]);
+    }
+
+    #[test]
+    fn minute_offsets_group_jobs_by_fixed_minute() {
+        let lines = [
+            "0 3 * * * a",
+            "0 4 * * * b",
+            "0 6 * * 1 c",
+            "30 5 * * * d",
+            "*/5 * * * * e",
+        ];
+        let (counts, varies) = minute_offset_counts(&lines);
+        assert_eq!(counts, BTreeMap::from([(0, 3), (30, 1)]));
+        assert_eq!(varies, 1);
+    }
+
+    #[test]
+    fn locale_count_groups_digits() {
+        let en = Some(Locale::en);
+        let style = ChartStyle::new(UnicodeMode::Never, CountPosition::Before, false, en);
+        assert_eq!(style.count(1234), "1,234");
+        assert_eq!(ascii_style().count(1234), "1234");
+    }
+
+    #[test]
+    fn terminal_width_comes_from_columns_or_defaults() {
+        assert_eq!(terminal_width_and_source(env(&[("COLUMNS", "132")])), (132, "$COLUMNS"));
+        assert_eq!(terminal_width_and_source(env(&[("COLUMNS", "0")])), (80, "default"));
+        assert_eq!(terminal_width_and_source(env(&[])), (80, "default"));
+    }
+
+    #[test]
+    fn specials_listing_matches_normalization() {
+        assert!(specials_lines().contains(&"@daily     0 0 * * *".to_string()));
+        for (name, cron) in SPECIALS {
+            let line = format!("{} /usr/bin/backup.sh", name);
+            assert_eq!(
+                normalize_special_entry(&line),
+                Some(format!("{} /usr/bin/backup.sh", cron))
+            );
+        }
+        assert_eq!(normalize_special_entry("@reboot /usr/bin/backup.sh"), None);
+    }
+}